@@ -3,6 +3,8 @@
 
 // Day Two, Cube Conundrum.
 
+use crate::utils::parse;
+
 type Game = Vec<(u32, u32, u32)>;
 
 fn solve1(games: &Vec<Game>, bag: (u32, u32, u32)) -> usize {
@@ -43,12 +45,9 @@ fn parse_input(records: &str) -> Vec<Game> {
     let mut games: Vec<Game> = Vec::new();
 
     for record in records.lines() {
-        let subsets: Vec<&str> = record
-            .split(": ")
-            .nth(1)
-            .expect("the game record is ill-formed")
-            .split("; ")
-            .collect();
+        let (_, subsets_str) = parse::key_value(record).expect("the game record is ill-formed");
+
+        let subsets: Vec<&str> = subsets_str.split("; ").collect();
 
         let mut game = Game::new();
 
@@ -58,18 +57,15 @@ fn parse_input(records: &str) -> Vec<Game> {
             let mut cube_quantities: (u32, u32, u32) = (0, 0, 0);
 
             for cube_str in cubes {
-                let mut cube = cube_str.split_whitespace();
-
-                let quantity: u32 = cube
-                    .next()
-                    .expect("the cube description should not be empty")
-                    .parse()
-                    .expect("the quantity should be a positive integer");
-
-                let cube_color = cube
-                    .next()
-                    .expect("there should be a color name following the quantity")
-                    .to_lowercase();
+                let (quantity_str, cube_color) = cube_str
+                    .trim()
+                    .split_once(' ')
+                    .expect("there should be a color name following the quantity");
+
+                let quantity: u32 =
+                    parse::int(quantity_str).expect("the quantity should be a positive integer");
+
+                let cube_color = cube_color.trim().to_lowercase();
 
                 match cube_color.as_str() {
                     "red" => cube_quantities.0 = quantity,
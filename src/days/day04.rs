@@ -5,6 +5,8 @@
 
 use std::collections::HashSet;
 
+use crate::utils::parse;
+
 type Card = (HashSet<u32>, HashSet<u32>);
 
 fn solve1(cards: &Vec<Card>) -> u32 {
@@ -54,32 +56,22 @@ fn parse_input(input_data: &str) -> Vec<Card> {
     let mut cards: Vec<Card> = Vec::new();
 
     for line in input_data.lines() {
-        let mut lists = line
-            .split(": ")
-            .nth(1)
-            .expect("there should be two lists of numbers after the card name")
-            .split(" | ");
-
-        let winning_nums = lists
-            .next()
-            .expect("there should be a list of winning numbers")
-            .split_whitespace()
-            .map(|n| {
-                n.parse::<u32>()
-                    .expect("this list should contain only numbers")
-            });
-
-        let owned_nums = lists
-            .next()
-            .expect("there should be a list of owned numbers")
-            .split_whitespace()
-            .map(|n| {
-                n.parse::<u32>()
-                    .expect("this list should contain only numbers")
-            });
-
-        let winning_set: HashSet<u32> = winning_nums.collect();
-        let owned_set: HashSet<u32> = owned_nums.collect();
+        let (_, lists) = parse::key_value(line)
+            .expect("there should be two lists of numbers after the card name");
+
+        let (winning_nums, owned_nums) = lists
+            .split_once(" | ")
+            .expect("there should be a list of winning numbers and a list of owned numbers");
+
+        let winning_set: HashSet<u32> = parse::ints(winning_nums)
+            .expect("this list should contain only numbers")
+            .into_iter()
+            .collect();
+
+        let owned_set: HashSet<u32> = parse::ints(owned_nums)
+            .expect("this list should contain only numbers")
+            .into_iter()
+            .collect();
 
         let card = (winning_set, owned_set);
         cards.push(card);
@@ -0,0 +1,94 @@
+// Copyright (C) 2023 Matheus Fernandes Bigolin <mfrdrbigolin@disroot.org>
+// SPDX-License-Identifier: MIT
+
+// Day Five, If You Give A Seed A Fertilizer.
+
+use crate::utils::{
+    interval::{Interval, IntervalSet, OffsetMap},
+    parse,
+};
+
+/// The chain of maps from seeds to locations, in order.
+type Almanac = Vec<OffsetMap<i64>>;
+
+fn solve1(seeds: &[i64], almanac: &Almanac) -> i64 {
+    seeds
+        .iter()
+        .map(|&seed| almanac.iter().fold(seed, |value, map| map.map(value)))
+        .min()
+        .expect("there should be at least one seed")
+}
+
+fn solve2(seeds: &[i64], almanac: &Almanac) -> i64 {
+    // The seeds come in (start, length) pairs, each describing a range.
+    let seed_ranges: IntervalSet<i64> = seeds
+        .chunks_exact(2)
+        .map(|pair| Interval::new(pair[0], pair[0] + pair[1]))
+        .collect();
+
+    // Map whole ranges at once, splitting them at the edges of each map.
+    let locations = almanac
+        .iter()
+        .fold(seed_ranges, |ranges, map| map.map_set(&ranges));
+
+    locations
+        .intervals()
+        .first()
+        .expect("there should be at least one seed range")
+        .start
+}
+
+fn parse_input(input_data: &str) -> (Vec<i64>, Almanac) {
+    let mut blocks = parse::blocks(input_data);
+
+    let (_, seeds_str) = blocks.next().expect("there should be a list of seeds");
+
+    let (_, seeds) = parse::key_ints(seeds_str).expect("the seeds should be integers");
+
+    let almanac = blocks
+        .map(|(_, block)| {
+            let mut lines = block.lines();
+
+            lines.next().expect("every map should have a header");
+
+            let mut map = OffsetMap::new();
+
+            for line in lines {
+                let [dest, source, len] = parse::ints(line)
+                    .expect("every map line should only have integers")[..]
+                else {
+                    panic!("every map line should have a destination, a source, and a length");
+                };
+
+                map.insert(Interval::new(source, source + len), dest - source);
+            }
+
+            map
+        })
+        .collect();
+
+    (seeds, almanac)
+}
+
+pub fn day05(input_data: &str) {
+    let (seeds, almanac) = parse_input(input_data);
+
+    let sol1 = solve1(&seeds, &almanac);
+    let sol2 = solve2(&seeds, &almanac);
+
+    println!("{sol1}");
+    println!("{sol2}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let (seeds, almanac) = parse_input(include_str!("../../inputs/day05.test.txt"));
+
+        assert_eq!(solve1(&seeds, &almanac), 35);
+        assert_eq!(solve2(&seeds, &almanac), 46);
+    }
+}
@@ -3,6 +3,8 @@
 
 // Day Six, Wait For It.
 
+use crate::utils::parse;
+
 /// Multiply the number of ways to win each race described in `document`.
 /// Essentially, for each race, solves by finding the roots of
 /// -x² + xt - d > 0, in which (t, d) ∈ `document`, with the quadratic formula.
@@ -26,13 +28,10 @@ fn solve(document: (Vec<u64>, Vec<u64>)) -> u64 {
 
 fn parse_input1(input_data: &str) -> (Vec<u64>, Vec<u64>) {
     let mut lines = input_data.lines().map(|line| {
-        line.split(":")
-            .nth(1)
-            .expect("a list of space-separated values should follow")
-            .trim()
-            .split_whitespace()
-            .map(|n| n.parse::<u64>().expect("this should be an integer"))
-            .collect()
+        let (_, values) =
+            parse::key_ints(line).expect("a list of space-separated integers should follow");
+
+        values
     });
 
     let times = lines
@@ -48,14 +47,12 @@ fn parse_input1(input_data: &str) -> (Vec<u64>, Vec<u64>) {
 
 fn parse_input2(input_data: &str) -> (Vec<u64>, Vec<u64>) {
     let mut lines = input_data.lines().map(|line| {
-        line.split(":")
-            .nth(1)
-            .expect("a list of space-separated values should follow")
-            .chars()
-            .filter(|ch| !ch.is_whitespace())
-            .collect::<String>()
-            .parse::<u64>()
-            .expect("this should be an integer")
+        let (_, values) =
+            parse::key_value(line).expect("a list of space-separated values should follow");
+
+        let value: String = values.chars().filter(|ch| !ch.is_whitespace()).collect();
+
+        parse::int::<u64>(&value).expect("this should be an integer")
     });
 
     let time = lines
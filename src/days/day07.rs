@@ -7,48 +7,41 @@ use core::cmp::Ordering;
 use std::collections::HashMap;
 use std::str::FromStr;
 
-#[derive(Copy, Clone, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+use super::Problem;
+use crate::utils::{ParseTileError, Tile};
+
+#[derive(Copy, Clone, PartialEq, PartialOrd, Eq, Ord, Debug, Hash, Tile)]
 enum Card {
+    #[tile('★', internal)]
     Joker,
+    #[tile('2')]
     N2,
+    #[tile('3')]
     N3,
+    #[tile('4')]
     N4,
+    #[tile('5')]
     N5,
+    #[tile('6')]
     N6,
+    #[tile('7')]
     N7,
+    #[tile('8')]
     N8,
+    #[tile('9')]
     N9,
+    #[tile('T')]
     T,
+    #[tile('J')]
     Jack,
+    #[tile('Q')]
     Q,
+    #[tile('K')]
     K,
+    #[tile('A')]
     A,
 }
 
-impl FromStr for Card {
-    type Err = ();
-
-    fn from_str(card_str: &str) -> Result<Card, Self::Err> {
-        match card_str {
-            "★" => Ok(Card::Joker),
-            "2" => Ok(Card::N2),
-            "3" => Ok(Card::N3),
-            "4" => Ok(Card::N4),
-            "5" => Ok(Card::N5),
-            "6" => Ok(Card::N6),
-            "7" => Ok(Card::N7),
-            "8" => Ok(Card::N8),
-            "9" => Ok(Card::N9),
-            "T" => Ok(Card::T),
-            "J" => Ok(Card::Jack),
-            "Q" => Ok(Card::Q),
-            "K" => Ok(Card::K),
-            "A" => Ok(Card::A),
-            _ => Err(()),
-        }
-    }
-}
-
 #[derive(PartialEq, PartialOrd, Eq, Ord, Debug)]
 enum HandType {
     High,
@@ -116,13 +109,13 @@ impl Hand {
 }
 
 impl FromStr for Hand {
-    type Err = ();
+    type Err = ParseTileError;
 
     fn from_str(cards_str: &str) -> Result<Hand, Self::Err> {
         let mut cards_vec: Vec<Card> = vec![];
 
-        for card_str in cards_str.chars() {
-            cards_vec.push(Card::from_str(card_str.to_string().as_str())?);
+        for card_ch in cards_str.chars() {
+            cards_vec.push(Card::from_char(card_ch)?);
         }
 
         let cards = cards_vec
@@ -189,6 +182,56 @@ fn parse_input(input_data: &str, j_is_joker: bool) -> Vec<(Hand, u32)> {
     plays
 }
 
+pub fn validate(input_data: &str) -> Vec<Problem> {
+    let mut problems = vec![];
+
+    for (i, line) in input_data.lines().enumerate() {
+        let line_number = i + 1;
+
+        let fields: Vec<&str> = line.split_whitespace().collect();
+
+        let [hand_str, bid_str] = fields[..] else {
+            problems.push(Problem::new(
+                line_number,
+                format!("expected a hand and a bid, found {} fields", fields.len()),
+            ));
+            continue;
+        };
+
+        for card_str in hand_str.chars() {
+            // The Joker symbol is only used internally.
+            if card_str == '★' || Card::from_char(card_str).is_err() {
+                problems.push(Problem::new(
+                    line_number,
+                    format!("'{card_str}' is not a card (expected one of 23456789TJQKA)"),
+                ));
+            }
+        }
+
+        let num_cards = hand_str.chars().count();
+
+        if num_cards != 5 {
+            problems.push(Problem::new(
+                line_number,
+                format!("the hand should have exactly five cards, found {num_cards}"),
+            ));
+        }
+
+        if matches!(bid_str.parse::<u32>(), Ok(0) | Err(_)) {
+            problems.push(Problem::new(
+                line_number,
+                format!("the bid '{bid_str}' should be a positive integer"),
+            ));
+        }
+    }
+
+    if problems.is_empty() && input_data.trim().is_empty() {
+        problems.push(Problem::global("there should be at least one hand"));
+    }
+
+    problems
+}
+
 pub fn day07(input_data: &str) {
     let plays_with_jack = parse_input(input_data, false);
 
@@ -3,9 +3,14 @@
 
 // Day Eight, Haunted Wasteland.
 
-use num::integer;
 use regex::Regex;
-use std::str::FromStr;
+use std::collections::HashMap;
+
+use super::Problem;
+use crate::utils::{
+    cycle::{self, Cycle},
+    math, Tile,
+};
 
 // For ease of development, we will only consider uppercase letters for the alphabet.
 const NODE_ALPHABET_SIZE: usize = 'Z' as usize - 'A' as usize + 1;
@@ -13,23 +18,14 @@ const NODE_IDENTIFIER_SIZE: usize = 3;
 /// Number of all the possible nodes in the network.
 const NETWORK_SIZE: usize = NODE_ALPHABET_SIZE.pow(NODE_IDENTIFIER_SIZE as u32);
 
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, Tile)]
 enum Instruction {
+    #[tile('L', 'l')]
     Left,
+    #[tile('R', 'r')]
     Right,
 }
 
-impl FromStr for Instruction {
-    type Err = ();
-
-    fn from_str(instr_str: &str) -> Result<Instruction, Self::Err> {
-        match instr_str.to_uppercase().as_str() {
-            "L" => Ok(Instruction::Left),
-            "R" => Ok(Instruction::Right),
-            _ => Err(()),
-        }
-    }
-}
-
 /// Given a node string identifier, return the node’s index in the network.
 fn get_node_index(node: &str) -> usize {
     node.chars()
@@ -67,59 +63,80 @@ fn solve1(
     count as u64
 }
 
-// This solution is not general; it makes several assumptions that are true in the given input,
-// such as: predicting that there will be a cycle and that each cycle has the same period, but
-// that are not universally applicable.
-//
-// Considering I’m kinda late this year and that the general solution is quite more involved,
-// for now I will leave this as it is.
-fn solve2(
+/// Return the arrival patterns (as in `math::combine_arrivals`) of a ghost
+/// starting at `start_node` in the nodes ending with `end_nodes_idx`.
+fn get_arrivals(
     (left, right): &Network,
-    instructions: &Vec<Instruction>,
+    instructions: &[Instruction],
+    start_node: usize,
+    end_nodes_idx: usize,
+) -> Vec<(i64, i64)> {
+    // The ghost’s walk is determined by its node and its position in the
+    // instructions, so the walk eventually cycles through these states.
+    let history = cycle::find_cycle((start_node, 0), |&(node, ins)| {
+        let next_node = match instructions[ins] {
+            Instruction::Left => left[node],
+            Instruction::Right => right[node],
+        }
+        .expect("node path should not lead to nonexistent node");
+
+        (next_node, (ins + 1) % instructions.len())
+    });
+
+    let Cycle { start, len } = history.cycle;
+
+    history
+        .states
+        .iter()
+        .enumerate()
+        .filter(|(_, &(node, _))| node % NODE_ALPHABET_SIZE == end_nodes_idx)
+        .map(|(step, _)| {
+            // Arrivals before the cycle only happen once.
+            let period = if step < start { 0 } else { len };
+
+            (step as i64, period as i64)
+        })
+        .collect()
+}
+
+fn solve2(
+    network: &Network,
+    instructions: &[Instruction],
     start_nodes_ending_with: char,
     end_nodes_ending_with: char,
 ) -> u64 {
     let start_nodes_idx = start_nodes_ending_with.to_ascii_uppercase() as usize - 'A' as usize;
     let end_nodes_idx = end_nodes_ending_with.to_ascii_uppercase() as usize - 'A' as usize;
 
-    let mut starting_nodes: Vec<usize> = vec![];
-
-    for (i, node) in left
-        .iter()
-        .copied()
-        .skip(start_nodes_idx)
+    let starting_nodes = (start_nodes_idx..NETWORK_SIZE)
         .step_by(NODE_ALPHABET_SIZE)
-        .enumerate()
-    {
-        if !node.is_none() {
-            starting_nodes.push(NODE_ALPHABET_SIZE * i + start_nodes_idx);
-        }
-    }
+        .filter(|&node| network.0[node].is_some());
 
-    let mut counts: Vec<u64> = vec![];
+    // The arrival patterns in which every ghost seen so far is on an end node.
+    let mut common_arrivals: Option<Vec<(i64, i64)>> = None;
 
     for node in starting_nodes {
-        let mut count = 0;
-
-        let mut cur = node;
-
-        while cur % NODE_ALPHABET_SIZE != end_nodes_idx {
-            cur = match instructions[count % instructions.len()] {
-                Instruction::Left => left[cur],
-                Instruction::Right => right[cur],
-            }
-            .expect("node path should not lead to nonexistent node");
-
-            count += 1;
-        }
-
-        counts.push(count as u64);
+        let arrivals = get_arrivals(network, instructions, node, end_nodes_idx);
+
+        common_arrivals = Some(match common_arrivals {
+            None => arrivals,
+            Some(common) => common
+                .iter()
+                .flat_map(|&c| {
+                    arrivals
+                        .iter()
+                        .filter_map(move |&a| math::combine_arrivals(c, a))
+                })
+                .collect(),
+        });
     }
 
-    counts
-        .into_iter()
-        .reduce(|multiple, cnt| integer::lcm(multiple, cnt))
+    common_arrivals
         .expect("there should be at least one starting node")
+        .into_iter()
+        .map(|(offset, _)| offset as u64)
+        .min()
+        .expect("the ghosts should reach end nodes at the same time")
 }
 
 fn parse_input(input_data: &str) -> (Vec<Instruction>, Network) {
@@ -137,8 +154,7 @@ fn parse_input(input_data: &str) -> (Vec<Instruction>, Network) {
         .expect("there should be a line with instructions")
         .chars()
         .map(|ins| {
-            Instruction::from_str(ins.to_string().as_str())
-                .expect("each instruction should be left or right only")
+            Instruction::from_char(ins).expect("each instruction should be left or right only")
         })
         .collect();
 
@@ -168,6 +184,80 @@ fn parse_input(input_data: &str) -> (Vec<Instruction>, Network) {
     (instructions, (left, right))
 }
 
+pub fn validate(input_data: &str) -> Vec<Problem> {
+    let mut problems = vec![];
+
+    let mut lines = input_data.lines().enumerate().map(|(i, line)| (i + 1, line));
+
+    match lines.next() {
+        Some((line_number, instructions_str)) => {
+            if instructions_str.is_empty() {
+                problems.push(Problem::new(line_number, "there should be instructions"));
+            }
+
+            for ins in instructions_str.chars() {
+                if Instruction::from_char(ins).is_err() {
+                    problems.push(Problem::new(
+                        line_number,
+                        format!("'{ins}' is not an instruction (expected L or R)"),
+                    ));
+                }
+            }
+        }
+        None => problems.push(Problem::global("there should be a line with instructions")),
+    };
+
+    if let Some((line_number, separator)) = lines.next() {
+        if !separator.is_empty() {
+            problems.push(Problem::new(
+                line_number,
+                "the instructions should be followed by a blank line",
+            ));
+        }
+    }
+
+    let node_regex = Regex::new(r"^([A-Z]{3}) = \(([A-Z]{3}), ([A-Z]{3})\)$")
+        .expect("hardcoded regex should be valid");
+
+    let mut definitions: HashMap<&str, usize> = HashMap::new();
+    let mut references: Vec<(usize, &str)> = vec![];
+
+    for (line_number, line) in lines {
+        let Some(captures) = node_regex.captures(line) else {
+            problems.push(Problem::new(
+                line_number,
+                format!("'{line}' should be a node in the form 'AAA = (BBB, CCC)'"),
+            ));
+            continue;
+        };
+
+        let (_, [node, left_node, right_node]) = captures.extract();
+
+        if let Some(first_line) = definitions.insert(node, line_number) {
+            problems.push(Problem::new(
+                line_number,
+                format!("the node {node} was already defined in line {first_line}"),
+            ));
+        }
+
+        references.push((line_number, left_node));
+        references.push((line_number, right_node));
+    }
+
+    for (line_number, node) in references {
+        if !definitions.contains_key(node) {
+            problems.push(Problem::new(
+                line_number,
+                format!("the node {node} is referenced but never defined"),
+            ));
+        }
+    }
+
+    problems.sort_by_key(|problem| problem.line);
+
+    problems
+}
+
 pub fn day08(input_data: &str) {
     let (instructions, network) = parse_input(input_data);
 
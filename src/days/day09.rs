@@ -3,35 +3,27 @@
 
 // Day Nine, Mirage Maintenance.
 
-fn solve(histories: Vec<Vec<i32>>) -> i32 {
-    let mut history_sum = 0;
+use crate::utils::{parse, poly};
 
-    for history in histories {
-        let mut next_value = 0;
-
-        let mut diffs = history;
-
-        // While the adjacent differences are non-constant.
-        while diffs.windows(2).any(|val| val[0] != val[1]) {
-            next_value += diffs[diffs.len() - 1];
-            diffs = diffs.windows(2).map(|val| val[1] - val[0]).collect();
-        }
-        next_value += diffs[diffs.len() - 1];
-
-        history_sum += next_value;
-    }
+/// Sum the values of each history extrapolated one step forward (after the
+/// last value) or backward (before the first value).
+fn solve(histories: &[Vec<i64>], forward: bool) -> i64 {
+    histories
+        .iter()
+        .map(|history| {
+            let x = if forward { history.len() as i64 } else { -1 };
 
-    history_sum
+            poly::value_at(history, x).expect("each history should have at least one value")
+        })
+        .sum()
 }
 
-fn parse_input(input_data: &str) -> Vec<Vec<i32>> {
-    let mut histories: Vec<Vec<i32>> = vec![];
+fn parse_input(input_data: &str) -> Vec<Vec<i64>> {
+    let mut histories: Vec<Vec<i64>> = vec![];
 
     for line in input_data.lines() {
-        let history: Vec<i32> = line
-            .split_whitespace()
-            .map(|n| n.parse().expect("each history value should be an integer"))
-            .collect();
+        let history: Vec<i64> =
+            parse::ints(line).expect("each history value should be an integer");
 
         histories.push(history);
     }
@@ -42,18 +34,8 @@ fn parse_input(input_data: &str) -> Vec<Vec<i32>> {
 pub fn day09(input_data: &str) {
     let histories = parse_input(input_data);
 
-    // Each history’s value in reverse, used for part two.
-    let histories_rev = histories
-        .iter()
-        .cloned()
-        .map(|mut lol| {
-            lol.reverse();
-            lol
-        })
-        .collect();
-
-    let sol1 = solve(histories);
-    let sol2 = solve(histories_rev);
+    let sol1 = solve(&histories, true);
+    let sol2 = solve(&histories, false);
 
     println!("{sol1}");
     println!("{sol2}");
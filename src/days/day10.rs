@@ -3,122 +3,134 @@
 
 // Day Ten, Pipe Maze.
 
-use crate::utils::{Direction, Matrix, Point};
+use crate::utils::{
+    image::{Frames, Rgb},
+    geometry, search, Direction, DirectionMap, Matrix, ParseTileError, Point, Tile,
+};
 
-use std::ops::{Index, IndexMut};
-use std::str::FromStr;
+use super::Problem;
 
 /// Pipe with cardinal directions representing connections. The default value
 /// of the Pipe type is a Pipe with no connections (i.e., the ground tile).
 #[derive(Clone, Copy, PartialEq, Eq, Default, Debug, Hash)]
 struct Pipe {
-    south: bool,
-    north: bool,
-    east: bool,
-    west: bool,
+    connections: DirectionMap<bool>,
 }
 
 impl Pipe {
+    pub fn new(dirs: &[Direction]) -> Self {
+        Pipe {
+            connections: DirectionMap::from_fn(|dir| dirs.contains(&dir)),
+        }
+    }
+
     pub fn is_connected(&self, other: Pipe, dir: Direction) -> bool {
-        self[dir] && other[!dir]
+        self.connections[dir] && other.connections[!dir]
     }
 }
 
-impl Index<Direction> for Pipe {
-    type Output = bool;
-
-    /// Verify whether a connection exists in the pipe.
-    fn index(&self, dir: Direction) -> &Self::Output {
-        match dir {
-            Direction::South => &self.south,
-            Direction::North => &self.north,
-            Direction::East => &self.east,
-            Direction::West => &self.west,
+impl Tile for Pipe {
+    fn from_char(tile_ch: char) -> Result<Pipe, ParseTileError> {
+        match tile_ch.to_ascii_uppercase() {
+            '.' => Ok(Pipe::new(&[])),
+            '|' => Ok(Pipe::new(&[Direction::South, Direction::North])),
+            '-' => Ok(Pipe::new(&[Direction::East, Direction::West])),
+            'F' => Ok(Pipe::new(&[Direction::South, Direction::East])),
+            '7' => Ok(Pipe::new(&[Direction::South, Direction::West])),
+            'L' => Ok(Pipe::new(&[Direction::North, Direction::East])),
+            'J' => Ok(Pipe::new(&[Direction::North, Direction::West])),
+            _ => Err(ParseTileError {
+                ch: tile_ch,
+                expected: ".|-F7LJ",
+            }),
         }
     }
-}
 
-impl IndexMut<Direction> for Pipe {
-    fn index_mut(&mut self, dir: Direction) -> &mut Self::Output {
-        match dir {
-            Direction::South => &mut self.south,
-            Direction::North => &mut self.north,
-            Direction::East => &mut self.east,
-            Direction::West => &mut self.west,
+    /// Print the pipe by its connections. Pipes with connections that have
+    /// no symbol (possible only for the starting tile) are printed as '?'.
+    fn to_char(&self) -> char {
+        let DirectionMap {
+            south,
+            north,
+            east,
+            west,
+        } = self.connections;
+
+        match (south, north, east, west) {
+            (false, false, false, false) => '.',
+            (true, true, false, false) => '|',
+            (false, false, true, true) => '-',
+            (true, false, true, false) => 'F',
+            (true, false, false, true) => '7',
+            (false, true, true, false) => 'L',
+            (false, true, false, true) => 'J',
+            _ => '?',
         }
     }
 }
 
-impl FromStr for Pipe {
-    type Err = ();
+/// State of a tile while walking the main loop, used to draw the frames of the walk.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug, Hash)]
+enum Walk {
+    #[default]
+    Ground,
+    Pipe,
+    Visited,
+    Head,
+}
 
-    fn from_str(tile_str: &str) -> Result<Pipe, Self::Err> {
-        match tile_str.to_uppercase().as_str() {
-            "." => Ok(Pipe {
-                ..Default::default()
-            }),
-            "|" => Ok(Pipe {
-                south: true,
-                north: true,
-                ..Default::default()
-            }),
-            "-" => Ok(Pipe {
-                east: true,
-                west: true,
-                ..Default::default()
-            }),
-            "F" => Ok(Pipe {
-                south: true,
-                east: true,
-                ..Default::default()
-            }),
-            "7" => Ok(Pipe {
-                south: true,
-                west: true,
-                ..Default::default()
-            }),
-            "L" => Ok(Pipe {
-                north: true,
-                east: true,
-                ..Default::default()
-            }),
-            "J" => Ok(Pipe {
-                north: true,
-                west: true,
-                ..Default::default()
-            }),
-            _ => Err(()),
+fn get_main_loop_points(
+    starting_point: Point,
+    pipe_matrix: &Matrix<Pipe>,
+    frames: Option<&mut Frames>,
+) -> Vec<Point> {
+    // Every tile of the main loop has exactly two connections, so a depth-first
+    // search from the starting point goes around the loop, tile by tile.
+    let main_loop = search::dfs(
+        [starting_point],
+        |&point| {
+            pipe_matrix
+                .get_neighbors(point)
+                .into_iter()
+                .filter(move |&(dir, neighbor)| {
+                    pipe_matrix[point].is_connected(pipe_matrix[neighbor], dir)
+                })
+                .map(|(_, neighbor)| neighbor)
+        },
+        |_| false,
+    )
+    .order;
+
+    if let Some(frames) = frames {
+        let mut walk_matrix = Matrix::<Walk>::new(pipe_matrix.rows, pipe_matrix.cols);
+
+        for (point, &pipe) in pipe_matrix.enumerate_points() {
+            if pipe != Pipe::default() {
+                walk_matrix[point] = Walk::Pipe;
+            }
         }
-    }
-}
 
-fn get_main_loop_points(starting_point: Point, pipe_matrix: &Matrix<Pipe>) -> Vec<Point> {
-    // Start in a neighboring tile of the starting point.
-    let (mut cur_dir, mut cur) = pipe_matrix.get_neighbors(starting_point)[0];
-
-    let mut visited = vec![cur];
-
-    while cur != starting_point {
-        // Gets the next point in the loop, cur_dir is used to prevent going
-        // back to a previously visited point.
-        (cur_dir, cur) = pipe_matrix
-            .get_neighbors(cur)
-            .iter()
-            .copied()
-            .filter(|(dir, point)| {
-                *dir != !cur_dir && pipe_matrix[cur].is_connected(pipe_matrix[*point], *dir)
-            })
-            .next()
-            .expect("the main loop should be continuous");
-
-        visited.push(cur);
+        for &point in &main_loop {
+            walk_matrix[point] = Walk::Head;
+
+            frames
+                .push(&walk_matrix, |walk| match walk {
+                    Walk::Ground => Rgb::BLACK,
+                    Walk::Pipe => Rgb::GRAY,
+                    Walk::Visited => Rgb::GREEN,
+                    Walk::Head => Rgb::RED,
+                })
+                .expect("the frame should be saved");
+
+            walk_matrix[point] = Walk::Visited;
+        }
     }
 
-    visited
+    main_loop
 }
 
-fn solve1(starting_point: Point, pipe_matrix: &Matrix<Pipe>) -> usize {
-    let main_loop = get_main_loop_points(starting_point, pipe_matrix);
+fn solve1(starting_point: Point, pipe_matrix: &Matrix<Pipe>, frames: Option<&mut Frames>) -> usize {
+    let main_loop = get_main_loop_points(starting_point, pipe_matrix, frames);
 
     // Considering that there will always be an even number of points that form
     // the main loop, the farthest distance is halfway through the loop.
@@ -126,24 +138,11 @@ fn solve1(starting_point: Point, pipe_matrix: &Matrix<Pipe>) -> usize {
 }
 
 fn solve2(starting_point: Point, pipe_matrix: &Matrix<Pipe>) -> usize {
-    let mut main_loop = get_main_loop_points(starting_point, pipe_matrix);
-
-    // The shoelace formula requires that the initial point also appears at the end.
-    main_loop.push(main_loop[0]);
+    let main_loop = get_main_loop_points(starting_point, pipe_matrix, None);
 
-    let mut loop_area: i32 = 0;
-
-    for point_pair in main_loop.windows(2) {
-        let [point1, point2] = [point_pair[0], point_pair[1]];
-
-        // The total area is incremented by the determinant of the points in the pair.
-        loop_area += (point1.0 * point2.1) as i32 - (point1.1 * point2.0) as i32;
-    }
-
-    loop_area /= 2;
-
-    // Use Pick’s theorem to find the number of inclosed points.
-    (loop_area.abs() as usize) - main_loop.len() / 2 + 1
+    // Every tile of the main loop is a vertex of the polygon, so the enclosed
+    // tiles are the lattice points strictly inside it.
+    geometry::interior_points(&main_loop) as usize
 }
 
 fn parse_input(input_data: &str) -> (Point, Matrix<Pipe>) {
@@ -157,7 +156,7 @@ fn parse_input(input_data: &str) -> (Point, Matrix<Pipe>) {
         for (j, tile_str) in line.chars().enumerate() {
             // The starting tile pipe will be set later.
             if tile_str != 'S' {
-                pipe_matrix[(i, j)] = Pipe::from_str(tile_str.to_string().as_str())
+                pipe_matrix[(i, j)] = Pipe::from_char(tile_str)
                     .expect("there should only be valid tiles in the sketch");
             }
 
@@ -166,7 +165,7 @@ fn parse_input(input_data: &str) -> (Point, Matrix<Pipe>) {
                     panic!("there should be only one starting tile in the sketch");
                 }
 
-                starting_point = Some((i, j));
+                starting_point = Some(Point::from((i, j)));
             }
         }
     }
@@ -175,18 +174,74 @@ fn parse_input(input_data: &str) -> (Point, Matrix<Pipe>) {
 
     // Find the right connections for the starting point.
     for (dir, neighbor) in pipe_matrix.get_neighbors(starting_point) {
-        if pipe_matrix[neighbor][!dir] {
-            pipe_matrix[starting_point][dir] = true;
+        if pipe_matrix[neighbor].connections[!dir] {
+            pipe_matrix[starting_point].connections[dir] = true;
         }
     }
 
     (starting_point, pipe_matrix)
 }
 
+pub fn validate(input_data: &str) -> Vec<Problem> {
+    let mut problems = vec![];
+
+    let mut starting_lines = vec![];
+
+    let cols = input_data.lines().next().unwrap_or("").chars().count();
+
+    for (i, line) in input_data.lines().enumerate() {
+        let line_number = i + 1;
+
+        let len = line.chars().count();
+
+        if len != cols {
+            problems.push(Problem::new(
+                line_number,
+                format!("the row has {len} tiles, but the first row has {cols}"),
+            ));
+        }
+
+        for tile_str in line.chars() {
+            if tile_str == 'S' {
+                starting_lines.push(line_number);
+            } else if Pipe::from_char(tile_str).is_err() {
+                problems.push(Problem::new(
+                    line_number,
+                    format!("'{tile_str}' is not a tile (expected one of .|-FJL7S)"),
+                ));
+            }
+        }
+    }
+
+    if cols == 0 {
+        problems.push(Problem::global("the sketch should not be empty"));
+    }
+
+    match starting_lines[..] {
+        [] => problems.push(Problem::global("there should be one starting tile ('S')")),
+        [_] => (),
+        [_, ref extra @ ..] => {
+            for &line_number in extra {
+                problems.push(Problem::new(
+                    line_number,
+                    "there should be only one starting tile ('S') in the sketch",
+                ));
+            }
+        }
+    };
+
+    problems.sort_by_key(|problem| problem.line);
+
+    problems
+}
+
 pub fn day10(input_data: &str) {
     let (starting_point, pipe_matrix) = parse_input(input_data);
 
-    let sol1 = solve1(starting_point, &pipe_matrix);
+    // Set ADVENT_FRAMES to save the walk through the main loop as frames.
+    let mut frames = Frames::from_env("day10");
+
+    let sol1 = solve1(starting_point, &pipe_matrix, frames.as_mut());
     let sol2 = solve2(starting_point, &pipe_matrix);
 
     println!("{sol1}");
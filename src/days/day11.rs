@@ -3,102 +3,73 @@
 
 // Day Eleven, Cosmic Expansion.
 
-use std::str::FromStr;
+use std::{collections::HashSet, str::FromStr};
 
-use crate::utils::{Matrix, Point};
+use crate::utils::{Matrix, Point, SparseGrid, Vector};
+
+fn solve(galaxies: &SparseGrid<char>) -> u64 {
+    let points: Vec<_> = galaxies.points().collect();
 
-fn solve(points: &Vec<Point>) -> u64 {
     let mut dist_sum = 0;
 
     for (i, point1) in points.iter().copied().enumerate() {
         for point2 in points[i + 1..].iter().copied() {
-            let dx = (point2.0 as i64 - point1.0 as i64).abs() as u64;
-            let dy = (point2.1 as i64 - point1.1 as i64).abs() as u64;
-
-            // Manhattan distance.
-            dist_sum += dx + dy;
+            dist_sum += point1.manhattan(point2) as u64;
         }
     }
 
     dist_sum
 }
 
-fn get_empty_lines(intergalactic_image: &Matrix<char>) -> (Vec<usize>, Vec<usize>) {
-    let empty_rows: Vec<_> = intergalactic_image
-        .get_rows()
-        .into_iter()
-        .enumerate()
-        .filter(|(_, row)| !row.contains(&'#'))
-        .map(|(i, _)| i)
-        .rev()
-        .collect();
-
-    let empty_cols: Vec<_> = intergalactic_image
-        .get_cols()
-        .into_iter()
-        .enumerate()
-        .filter(|(_, col)| !col.contains(&'#'))
-        .map(|(j, _)| j)
-        .rev()
-        .collect();
-
-    (empty_rows, empty_cols)
-}
-
-fn expand_image(
-    intergalactic_image: &Matrix<char>,
-    (empty_rows, empty_cols): &(Vec<usize>, Vec<usize>),
-    expansion_factor: u32,
-) -> Vec<Point> {
-    let mut points = vec![];
-
-    let mut empty_rows = empty_rows.clone();
-    let mut cur_missing_row = empty_rows.pop();
-
-    let mut row_offset = 0;
-
-    for (i, line) in intergalactic_image.get_rows().into_iter().enumerate() {
-        if Some(i) == cur_missing_row {
-            row_offset += (expansion_factor - 1) as usize;
-            cur_missing_row = empty_rows.pop();
-            continue;
-        }
-
-        let mut empty_cols = empty_cols.clone();
-        let mut cur_missing_col = empty_cols.pop();
+/// Return, for each coordinate in `min..=max`, how many coordinates before it
+/// are empty (i.e., hold no galaxy).
+fn get_empty_counts(occupied: &HashSet<i64>, min: i64, max: i64) -> Vec<i64> {
+    (min..=max)
+        .scan(0, |empty_count, k| {
+            let count = *empty_count;
 
-        let mut col_offset = 0;
-
-        for (j, el) in line.into_iter().enumerate() {
-            if Some(j) == cur_missing_col {
-                col_offset += (expansion_factor - 1) as usize;
-                cur_missing_col = empty_cols.pop();
-                continue;
-            }
-
-            if el != '#' {
-                continue;
+            if !occupied.contains(&k) {
+                *empty_count += 1;
             }
 
-            points.push((i + row_offset, j + col_offset));
-        }
-    }
+            Some(count)
+        })
+        .collect()
+}
 
-    points
+/// Place the galaxies on the expanded plane, in which every empty row and
+/// column is replaced by `expansion_factor` of them.
+fn expand_image(galaxies: &SparseGrid<char>, expansion_factor: i64) -> SparseGrid<char> {
+    let Some((min, max)) = galaxies.bounds() else {
+        return SparseGrid::new();
+    };
+
+    let occupied_rows = galaxies.points().map(|Point(i, _)| i).collect();
+    let occupied_cols = galaxies.points().map(|Point(_, j)| j).collect();
+
+    let empty_rows = get_empty_counts(&occupied_rows, min.0, max.0);
+    let empty_cols = get_empty_counts(&occupied_cols, min.1, max.1);
+
+    galaxies
+        .iter()
+        .map(|(&point, &galaxy)| {
+            let offset = Vector(
+                empty_rows[(point.0 - min.0) as usize],
+                empty_cols[(point.1 - min.1) as usize],
+            );
+
+            (point + offset * (expansion_factor - 1), galaxy)
+        })
+        .collect()
 }
 
 pub fn day11(input_data: &str) {
     let intergalactic_image = Matrix::<char>::from_str(input_data).unwrap();
 
-    let empty_lines = get_empty_lines(&intergalactic_image);
-
-    let galaxy_points1 = expand_image(&intergalactic_image, &empty_lines, 2);
-
-    let sol1 = solve(&galaxy_points1);
-
-    let galaxy_points2 = expand_image(&intergalactic_image, &empty_lines, 1000000);
+    let galaxies = SparseGrid::from_matrix(&intergalactic_image, |&ch| ch == '#');
 
-    let sol2 = solve(&galaxy_points2);
+    let sol1 = solve(&expand_image(&galaxies, 2));
+    let sol2 = solve(&expand_image(&galaxies, 1000000));
 
     println!("{sol1}");
     println!("{sol2}");
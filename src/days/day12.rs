@@ -3,28 +3,18 @@
 
 // Day Twelve, Hot Springs.
 
-use std::str::FromStr;
+use crate::utils::Tile;
 
-#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash, Tile)]
 enum Spring {
+    #[tile('.')]
     Operational,
+    #[tile('#')]
     Damaged,
+    #[tile('?')]
     Unknown,
 }
 
-impl FromStr for Spring {
-    type Err = ();
-
-    fn from_str(spring_str: &str) -> Result<Spring, Self::Err> {
-        match spring_str {
-            "." => Ok(Spring::Operational),
-            "#" => Ok(Spring::Damaged),
-            "?" => Ok(Spring::Unknown),
-            _ => Err(()),
-        }
-    }
-}
-
 type SpringRecord = (Vec<Spring>, Vec<u64>);
 
 // My previous recursive solution with memoization. It is roughly five times
@@ -162,10 +152,7 @@ fn parse_input(input_data: &str, unfolding_factor: u64) -> Vec<SpringRecord> {
 
         let springs = unfolded_springs
             .chars()
-            .map(|spring| {
-                Spring::from_str(spring.to_string().as_str())
-                    .expect("the spring condition should be valid")
-            })
+            .map(|spring| Spring::from_char(spring).expect("the spring condition should be valid"))
             .collect();
 
         let unfolded_groups = vec![groups_str.to_string(); unfolding_factor as usize].join(",");
@@ -3,40 +3,29 @@
 
 // Day Thirteen, Point of Incidence.
 
-use std::{cmp::min, iter::zip, str::FromStr};
+use std::{cmp::min, str::FromStr};
 
-use crate::utils::Matrix;
+use crate::utils::{parse, BitGrid, Matrix, Tile};
 
-#[derive(Copy, Clone, PartialEq, Eq, Default, Debug, Hash)]
+use super::Problem;
+
+#[derive(Copy, Clone, PartialEq, Eq, Default, Debug, Hash, Tile)]
 enum Terrain {
     #[default]
+    #[tile('.')]
     Ash,
+    #[tile('#')]
     Rock,
 }
 
-impl FromStr for Terrain {
-    type Err = ();
-
-    fn from_str(terrain_str: &str) -> Result<Terrain, Self::Err> {
-        match terrain_str {
-            "." => Ok(Terrain::Ash),
-            "#" => Ok(Terrain::Rock),
-            _ => Err(()),
-        }
-    }
-}
+/// Find the line of reflection between the rows of a landscape (given by its
+/// rocks) with exactly `req_num_smudges` differing tiles.
+fn find_reflection_line(rocks: &BitGrid, req_num_smudges: u32) -> Option<usize> {
+    for i in 1..rocks.rows {
+        let num_lines = min(i, rocks.rows - i);
 
-fn find_reflection_line(lines: &Vec<Vec<Terrain>>, req_num_smudges: usize) -> Option<usize> {
-    for i in 1..lines.len() {
-        let num_lines = min(i, lines.len() - i);
-
-        let num_smudges: usize = (0..num_lines)
-            .filter(|&j| lines[i - j - 1] != lines[i + j])
-            .map(|j| {
-                zip(&lines[i - j - 1], &lines[i + j])
-                    .filter(|&(a, b)| a != b)
-                    .count()
-            })
+        let num_smudges: u32 = (0..num_lines)
+            .map(|j| rocks.row_diff(i - j - 1, i + j))
             .sum();
 
         if num_smudges == req_num_smudges {
@@ -47,10 +36,13 @@ fn find_reflection_line(lines: &Vec<Vec<Terrain>>, req_num_smudges: usize) -> Op
     None
 }
 
-fn solve(landscape_matrices: &Vec<Matrix<Terrain>>, req_num_smudges: usize) -> usize {
+fn solve(landscape_matrices: &Vec<Matrix<Terrain>>, req_num_smudges: u32) -> usize {
     landscape_matrices.iter().fold(0, |acc, landscape_matrix| {
-        let rows = landscape_matrix.get_rows();
-        let cols = landscape_matrix.get_cols();
+        let is_rock = |&terrain: &Terrain| terrain == Terrain::Rock;
+
+        // The columns of the landscape are the rows of its transpose.
+        let rows = BitGrid::from_matrix(landscape_matrix, is_rock);
+        let cols = BitGrid::from_matrix(&landscape_matrix.transpose(), is_rock);
 
         let reflection_row = find_reflection_line(&rows, req_num_smudges);
         let reflection_col = find_reflection_line(&cols, req_num_smudges);
@@ -60,15 +52,63 @@ fn solve(landscape_matrices: &Vec<Matrix<Terrain>>, req_num_smudges: usize) -> u
 }
 
 fn parse_input(input_data: &str) -> Vec<Matrix<Terrain>> {
-    input_data
-        .split("\n\n")
-        .map(|matrix_str| {
+    parse::blocks(input_data)
+        .map(|(_, matrix_str)| {
             Matrix::from_str(matrix_str)
                 .expect("a landscape matrix should only contain ash ('.') and rocks ('#')")
         })
         .collect()
 }
 
+pub fn validate(input_data: &str) -> Vec<Problem> {
+    let mut problems = vec![];
+
+    // Width of the current block and the line in which it starts.
+    let mut block: Option<(usize, usize)> = None;
+
+    for (i, line) in input_data.lines().enumerate() {
+        let line_number = i + 1;
+
+        let len = line.chars().count();
+
+        if len == 0 {
+            if block.is_none() {
+                problems.push(Problem::new(line_number, "there should be no empty blocks"));
+            }
+
+            block = None;
+            continue;
+        }
+
+        let (cols, block_start) = *block.get_or_insert((len, line_number));
+
+        if len != cols {
+            problems.push(Problem::new(
+                line_number,
+                format!(
+                    "the row has {len} tiles, but the block starting in line {block_start} \
+                     has {cols}"
+                ),
+            ));
+        }
+
+        for terrain_str in line.chars() {
+            if Terrain::from_char(terrain_str).is_err() {
+                problems.push(Problem::new(
+                    line_number,
+                    format!("'{terrain_str}' is not ash ('.') or rock ('#')"),
+                ));
+            }
+        }
+    }
+
+    if input_data.is_empty() {
+        problems.push(Problem::global("there should be at least one block"));
+    }
+
+    problems
+}
+
 pub fn day13(input_data: &str) {
     let landscape_matrices = parse_input(input_data);
 
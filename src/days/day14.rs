@@ -0,0 +1,125 @@
+// Copyright (C) 2023 Matheus Fernandes Bigolin <mfrdrbigolin@disroot.org>
+// SPDX-License-Identifier: MIT
+
+// Day Fourteen, Parabolic Reflector Dish.
+
+use std::str::FromStr;
+
+use crate::utils::{cycle, Direction, Matrix, Point, Tile};
+
+#[derive(Copy, Clone, PartialEq, Eq, Default, Debug, Hash, Tile)]
+enum Rock {
+    #[default]
+    #[tile('.')]
+    Empty,
+    #[tile('O')]
+    Round,
+    #[tile('#')]
+    Cube,
+}
+
+/// Tilt the platform towards `dir`, rolling every round rock as far as it goes.
+fn tilt(platform: &mut Matrix<Rock>, dir: Direction) {
+    let (m, n) = (platform.rows as i64, platform.cols as i64);
+
+    // Each lane (a row or a column) is walked from the edge towards which the
+    // rocks roll.
+    let starts: Vec<Point> = match dir {
+        Direction::North => (0..n).map(|j| Point(0, j)).collect(),
+        Direction::South => (0..n).map(|j| Point(m - 1, j)).collect(),
+        Direction::West => (0..m).map(|i| Point(i, 0)).collect(),
+        Direction::East => (0..m).map(|i| Point(i, n - 1)).collect(),
+    };
+
+    let step = (!dir).delta();
+
+    for start in starts {
+        // The point where the next round rock of the lane will stop.
+        let mut free = start;
+        let mut point = start;
+
+        while platform.contains(point) {
+            match platform[point] {
+                Rock::Cube => free = point + step,
+                Rock::Round => {
+                    platform[point] = Rock::Empty;
+                    platform[free] = Rock::Round;
+                    free += step;
+                }
+                Rock::Empty => (),
+            }
+
+            point += step;
+        }
+    }
+}
+
+/// Tilt the platform North, West, South, and then East.
+fn spin_cycle(platform: &Matrix<Rock>) -> Matrix<Rock> {
+    let mut platform = platform.clone();
+
+    for dir in [
+        Direction::North,
+        Direction::West,
+        Direction::South,
+        Direction::East,
+    ] {
+        tilt(&mut platform, dir);
+    }
+
+    platform
+}
+
+/// Each round rock puts a load on the north support beams equal to the number
+/// of rows from it to the south edge, including its own.
+fn get_north_load(platform: &Matrix<Rock>) -> usize {
+    platform
+        .rows()
+        .enumerate()
+        .map(|(i, row)| {
+            let round_rocks = row.iter().filter(|&&rock| rock == Rock::Round).count();
+
+            (platform.rows - i) * round_rocks
+        })
+        .sum()
+}
+
+fn solve1(platform: &Matrix<Rock>) -> usize {
+    let mut platform = platform.clone();
+
+    tilt(&mut platform, Direction::North);
+
+    get_north_load(&platform)
+}
+
+fn solve2(platform: &Matrix<Rock>, num_cycles: usize) -> usize {
+    // The platform soon repeats itself, so skip the cycles after that.
+    let platform = cycle::nth_state(platform.clone(), spin_cycle, num_cycles);
+
+    get_north_load(&platform)
+}
+
+pub fn day14(input_data: &str) {
+    let platform =
+        Matrix::<Rock>::from_str(input_data).expect("the platform should only contain rocks");
+
+    let sol1 = solve1(&platform);
+    let sol2 = solve2(&platform, 1_000_000_000);
+
+    println!("{sol1}");
+    println!("{sol2}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let platform = Matrix::<Rock>::from_str(include_str!("../../inputs/day14.test.txt"))
+            .expect("the example should only contain rocks");
+
+        assert_eq!(solve1(&platform), 136);
+        assert_eq!(solve2(&platform, 1_000_000_000), 64);
+    }
+}
@@ -5,6 +5,8 @@
 
 use std::collections::HashMap;
 
+use crate::utils::parse;
+
 fn compute_hash(s: &str) -> u32 {
     s.chars().fold(0, |acc, ch| 17 * (acc + ch as u32) % 256)
 }
@@ -55,21 +57,13 @@ fn parse_steps2(input_data: &str) -> Vec<(&str, Option<u32>)> {
     input_data
         .split(',')
         .map(|step| {
-            let mut split_step = step.split(|ch| ch == '-' || ch == '=');
-
-            let label = split_step
-                .next()
-                .expect("there should be a label with every step");
-
-            let focal_length = match split_step
-                .next()
-                .expect("there should be a focal length with every step")
-            {
-                "" => None,
-                len_str => Some(
-                    len_str
-                        .parse()
-                        .expect("the focal length should be a positive integer"),
+            let (label, op, len_str) = parse::label_op(step, &['=', '-'])
+                .expect("there should be an operation after every label");
+
+            let focal_length = match op {
+                '-' => None,
+                _ => Some(
+                    parse::int(len_str).expect("the focal length should be a positive integer"),
                 ),
             };
 
@@ -3,146 +3,156 @@
 
 // Day Sixteen, The Floor Will Be Lava.
 
-use std::{cmp::max, collections::HashSet, str::FromStr};
-
-use crate::utils::{Direction, Matrix, Point};
-
-#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
-
-struct Mirror {
-    reflect: fn(Direction) -> Vec<Direction>,
+use std::{cmp::max, str::FromStr};
+
+use crate::utils::{
+    image::{Frames, Rgb},
+    search, BitGrid, Direction, DirectionMap, Matrix, Point, Tile,
+};
+
+/// A tile of the contraption: empty space, a mirror, or a splitter.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, Tile)]
+enum Mirror {
+    #[tile('.')]
+    Empty,
+    #[tile('|')]
+    VerticalSplitter,
+    #[tile('-')]
+    HorizontalSplitter,
+    #[tile('/')]
+    Slash,
+    #[tile('\\')]
+    Backslash,
 }
 
-impl Default for Mirror {
-    fn default() -> Self {
-        Mirror {
-            reflect: |dir: Direction| vec![!dir],
+impl Mirror {
+    /// Return the directions a beam heading towards `dir` takes after
+    /// reaching this tile.
+    pub fn reflect(&self, dir: Direction) -> Vec<Direction> {
+        let is_vertical = matches!(dir, Direction::South | Direction::North);
+
+        match self {
+            Mirror::Empty => vec![dir],
+            Mirror::VerticalSplitter if is_vertical => vec![dir],
+            Mirror::HorizontalSplitter if !is_vertical => vec![dir],
+            Mirror::VerticalSplitter | Mirror::HorizontalSplitter => {
+                vec![dir.turn_left(), dir.turn_right()]
+            }
+            Mirror::Slash if is_vertical => vec![dir.turn_right()],
+            Mirror::Slash => vec![dir.turn_left()],
+            Mirror::Backslash if is_vertical => vec![dir.turn_left()],
+            Mirror::Backslash => vec![dir.turn_right()],
         }
     }
 }
 
-impl FromStr for Mirror {
-    type Err = ();
-
-    fn from_str(tile_str: &str) -> Result<Mirror, Self::Err> {
-        match tile_str.to_uppercase().as_str() {
-            "." => Ok(Mirror {
-                reflect: |dir: Direction| vec![dir],
-            }),
-            "|" => Ok(Mirror {
-                reflect: |dir: Direction| match dir {
-                    Direction::South | Direction::North => vec![dir],
-                    _ => vec![Direction::South, Direction::North],
-                },
-            }),
-            "-" => Ok(Mirror {
-                reflect: |dir: Direction| match dir {
-                    Direction::East | Direction::West => vec![dir],
-                    _ => vec![Direction::East, Direction::West],
-                },
-            }),
-            "/" => Ok(Mirror {
-                reflect: |dir: Direction| match dir {
-                    Direction::South => vec![Direction::West],
-                    Direction::North => vec![Direction::East],
-                    Direction::East => vec![Direction::North],
-                    Direction::West => vec![Direction::South],
-                },
-            }),
-            "\\" => Ok(Mirror {
-                reflect: |dir: Direction| match dir {
-                    Direction::South => vec![Direction::East],
-                    Direction::North => vec![Direction::West],
-                    Direction::East => vec![Direction::South],
-                    Direction::West => vec![Direction::North],
-                },
-            }),
-            _ => Err(()),
-        }
-    }
+/// Beam state of a tile, used to draw the frames of the beam propagation.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug, Hash)]
+enum Beam {
+    #[default]
+    Dark,
+    Energized,
+    Head,
 }
 
+/// Return, for each Direction, the grid of points that a beam crossed while
+/// heading towards it.
 fn get_energized_positions(
     mirror_matrix: &Matrix<Mirror>,
     starting_position: (Direction, Point),
-) -> HashSet<(Direction, Point)> {
-    let mut visited: HashSet<(Direction, Point)> = HashSet::from([starting_position]);
-    let mut stack: Vec<(Direction, Point)> = vec![starting_position];
+    frames: Option<&mut Frames>,
+) -> DirectionMap<BitGrid> {
+    let mut visited =
+        DirectionMap::from_fn(|_| BitGrid::new(mirror_matrix.rows, mirror_matrix.cols));
+
+    let beam_positions = search::dfs_in([starting_position], &mut visited, |&(dir, point)| {
+        mirror_matrix[point]
+            .reflect(dir)
+            .into_iter()
+            .map(move |next_dir| (next_dir, point + next_dir.delta()))
+            .filter(|&(_, next_point)| mirror_matrix.contains(next_point))
+    });
 
-    while !stack.is_empty() {
-        let (dir, point) = stack.pop().expect("the stack should not be empty");
+    if let Some(frames) = frames {
+        let mut beam_matrix = Matrix::<Beam>::new(mirror_matrix.rows, mirror_matrix.cols);
 
-        let next_dirs = (mirror_matrix[point].reflect)(dir);
+        for &(_, point) in &beam_positions {
+            beam_matrix[point] = Beam::Head;
 
-        for pos in mirror_matrix
-            .get_neighbors(point)
-            .into_iter()
-            .filter(|(cur_dir, _)| next_dirs.contains(cur_dir))
-        {
-            if !visited.contains(&pos) {
-                stack.push(pos);
-            }
+            frames
+                .push(&beam_matrix, |beam| match beam {
+                    Beam::Dark => Rgb::BLACK,
+                    Beam::Energized => Rgb::YELLOW,
+                    Beam::Head => Rgb::RED,
+                })
+                .expect("the frame should be saved");
 
-            visited.insert(pos);
+            beam_matrix[point] = Beam::Energized;
         }
     }
 
     visited
 }
 
-fn solve1(mirror_matrix: &Matrix<Mirror>, starting_position: (Direction, Point)) -> usize {
-    let energized_positions = get_energized_positions(mirror_matrix, starting_position);
-
-    let energized_points: HashSet<Point> = energized_positions
-        .into_iter()
-        .map(|(_, point)| point)
-        .collect();
+/// Return the number of points crossed by a beam in any Direction.
+fn count_energized(visited: &DirectionMap<BitGrid>) -> usize {
+    BitGrid::union(visited.iter().map(|(_, grid)| grid))
+        .expect("there should be a grid for each direction")
+        .count()
+}
 
-    energized_points.len()
+fn solve1(
+    mirror_matrix: &Matrix<Mirror>,
+    starting_position: (Direction, Point),
+    frames: Option<&mut Frames>,
+) -> usize {
+    count_energized(&get_energized_positions(
+        mirror_matrix,
+        starting_position,
+        frames,
+    ))
 }
 
 // The only optimization used is the one that exclude edge points that were previously visited
-// (due to http://clb.confined.space/aoc2023). But, still quite slow (1.3 secs);
-// maybe someday I will try to optimize more.
+// (due to http://clb.confined.space/aoc2023). It used to be quite slow (1.3 secs), but with the
+// visited beams kept in bit grids, it now takes about 0.2 secs.
 fn solve2(mirror_matrix: &Matrix<Mirror>) -> usize {
     let mut max_energy = 0;
 
     let edge_points = {
         let south: Vec<Point> = (0..(mirror_matrix.cols - 1))
-            .map(|col| (mirror_matrix.rows - 1, col))
+            .map(|col| Point::from((mirror_matrix.rows - 1, col)))
+            .collect();
+        let north: Vec<Point> = (0..(mirror_matrix.cols - 1))
+            .map(|col| Point::from((0, col)))
             .collect();
-        let north: Vec<Point> = (0..(mirror_matrix.cols - 1)).map(|col| (0, col)).collect();
         let east: Vec<Point> = (1..(mirror_matrix.rows - 2))
-            .map(|row| (row, mirror_matrix.cols - 1))
+            .map(|row| Point::from((row, mirror_matrix.cols - 1)))
+            .collect();
+        let west: Vec<Point> = (1..(mirror_matrix.rows - 2))
+            .map(|row| Point::from((row, 0)))
             .collect();
-        let west: Vec<Point> = (1..(mirror_matrix.rows - 2)).map(|row| (row, 0)).collect();
 
         vec![south, north, east, west].concat()
     };
 
-    let mut visited_edges = HashSet::new();
+    // Only the edge points of these grids are ever looked up.
+    let mut visited_edges =
+        DirectionMap::from_fn(|_| BitGrid::new(mirror_matrix.rows, mirror_matrix.cols));
 
     for point in edge_points {
-        for dir in [
-            Direction::South,
-            Direction::North,
-            Direction::East,
-            Direction::West,
-        ] {
-            if visited_edges.contains(&(dir, point)) {
+        for dir in Direction::ALL {
+            if visited_edges[dir].get(point) {
                 continue;
             }
 
-            let visited_positions = get_energized_positions(mirror_matrix, (dir, point));
-
-            let energized_points: HashSet<Point> =
-                visited_positions.iter().map(|&(_, point)| point).collect();
+            let visited = get_energized_positions(mirror_matrix, (dir, point), None);
 
-            max_energy = max(max_energy, energized_points.len());
+            max_energy = max(max_energy, count_energized(&visited));
 
-            visited_edges.extend(visited_positions.into_iter().filter(|&(_, (i, j))| {
-                i == 0 || i == mirror_matrix.rows - 1 || j == 0 || j == mirror_matrix.cols - 1
-            }));
+            for dir in Direction::ALL {
+                visited_edges[dir].union_with(&visited[dir]);
+            }
         }
     }
 
@@ -153,7 +163,10 @@ pub fn day16(input_data: &str) {
     let mirror_matrix =
         Matrix::<Mirror>::from_str(input_data).expect("the mirror matrix should be valid");
 
-    let sol1 = solve1(&mirror_matrix, (Direction::East, (0, 0)));
+    // Set ADVENT_FRAMES to save the beam propagation of part one as frames.
+    let mut frames = Frames::from_env("day16");
+
+    let sol1 = solve1(&mirror_matrix, (Direction::East, Point::ORIGIN), frames.as_mut());
     let sol2 = solve2(&mirror_matrix);
 
     println!("{sol1}");
@@ -0,0 +1,89 @@
+// Copyright (C) 2023 Matheus Fernandes Bigolin <mfrdrbigolin@disroot.org>
+// SPDX-License-Identifier: MIT
+
+// Day Seventeen, Clumsy Crucible.
+
+use std::str::FromStr;
+
+use crate::utils::{search, Direction, Matrix, Point};
+
+/// A crucible on the city map: its position, the Direction it is heading to,
+/// and how many blocks it has moved in a straight line so far.
+type Crucible = (Point, Direction, usize);
+
+/// Find the least heat loss to move a crucible from the top-left block to the
+/// bottom-right one, moving at least `min_run` and at most `max_run` blocks in
+/// a straight line before turning (or stopping at the end).
+fn solve(heat_map: &Matrix<u8>, min_run: usize, max_run: usize) -> u32 {
+    let end = Point::from((heat_map.rows - 1, heat_map.cols - 1));
+
+    let sources: [Crucible; 2] = [
+        (Point::ORIGIN, Direction::East, 0),
+        (Point::ORIGIN, Direction::South, 0),
+    ];
+
+    search::dijkstra(
+        sources,
+        |&(point, dir, run)| {
+            // A crucible can go straight ahead, or turn left or right, but
+            // never go back.
+            [dir, dir.turn_left(), dir.turn_right()]
+                .into_iter()
+                .filter(move |&next_dir| {
+                    if next_dir == dir {
+                        run < max_run
+                    } else {
+                        run >= min_run
+                    }
+                })
+                .filter_map(move |next_dir| {
+                    let next = point + next_dir.delta();
+                    let next_run = if next_dir == dir { run + 1 } else { 1 };
+
+                    heat_map
+                        .get(next)
+                        .map(|&heat_loss| ((next, next_dir, next_run), u32::from(heat_loss)))
+                })
+        },
+        |&(point, _, run)| point == end && run >= min_run,
+    )
+    .goal_cost()
+    .expect("the crucible should be able to reach the end")
+}
+
+pub fn day17(input_data: &str) {
+    let heat_map =
+        Matrix::<u8>::from_str(input_data).expect("the heat loss of each block should be a digit");
+
+    let sol1 = solve(&heat_map, 0, 3);
+    let sol2 = solve(&heat_map, 4, 10);
+
+    println!("{sol1}");
+    println!("{sol2}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn heat_map(input_data: &str) -> Matrix<u8> {
+        Matrix::<u8>::from_str(input_data).expect("the heat loss of each block should be a digit")
+    }
+
+    #[test]
+    fn example() {
+        let heat_map = heat_map(include_str!("../../inputs/day17.test.txt"));
+
+        assert_eq!(solve(&heat_map, 0, 3), 102);
+        assert_eq!(solve(&heat_map, 4, 10), 94);
+    }
+
+    #[test]
+    fn ultra_crucible_stops_after_min_run() {
+        // The crucible can only stop at the end after moving at least four blocks
+        // in a straight line.
+        let heat_map = heat_map(include_str!("../../inputs/day17.test2.txt"));
+
+        assert_eq!(solve(&heat_map, 4, 10), 71);
+    }
+}
@@ -0,0 +1,80 @@
+// Copyright (C) 2023 Matheus Fernandes Bigolin <mfrdrbigolin@disroot.org>
+// SPDX-License-Identifier: MIT
+
+// Day Eighteen, Lavaduct Lagoon.
+
+use crate::utils::{
+    geometry::{self, Step},
+    Direction, Point,
+};
+
+/// The cubic meters of lava held by the lagoon dug by following the `steps`:
+/// its trench (the boundary) and everything inside it.
+fn solve(steps: &[Step]) -> i128 {
+    let vertices = geometry::trace(Point::ORIGIN, steps.iter().copied());
+
+    geometry::lattice_points(&vertices)
+}
+
+/// Decode the real step hidden in a color: the first five hexadecimal digits
+/// are the length, and the last one is the Direction.
+fn decode_color(color_str: &str) -> Step {
+    let hex = color_str
+        .trim_start_matches("(#")
+        .trim_end_matches(')');
+
+    let (Some(len_str), Some(dir_str)) = (hex.get(..5), hex.get(5..)) else {
+        panic!("the color '{color_str}' should have six digits");
+    };
+
+    let len = i64::from_str_radix(len_str, 16).expect("the length should be hexadecimal");
+
+    let dir = match dir_str {
+        "0" => Direction::East,
+        "1" => Direction::South,
+        "2" => Direction::West,
+        "3" => Direction::North,
+        _ => panic!("'{dir_str}' is not a direction (expected one of 0123)"),
+    };
+
+    Step { dir, len }
+}
+
+fn parse_input(input_data: &str) -> (Vec<Step>, Vec<Step>) {
+    input_data
+        .lines()
+        .map(|line| {
+            let step: Step = line.parse().expect("the step should be a direction and a length");
+
+            let color_str = line
+                .split_whitespace()
+                .nth(2)
+                .expect("there should be a color after the length");
+
+            (step, decode_color(color_str))
+        })
+        .unzip()
+}
+
+pub fn day18(input_data: &str) {
+    let (steps, color_steps) = parse_input(input_data);
+
+    let sol1 = solve(&steps);
+    let sol2 = solve(&color_steps);
+
+    println!("{sol1}");
+    println!("{sol2}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let (steps, color_steps) = parse_input(include_str!("../../inputs/day18.test.txt"));
+
+        assert_eq!(solve(&steps), 62);
+        assert_eq!(solve(&color_steps), 952408144115);
+    }
+}
@@ -0,0 +1,359 @@
+// Copyright (C) 2023 Matheus Fernandes Bigolin <mfrdrbigolin@disroot.org>
+// SPDX-License-Identifier: MIT
+
+// Day Nineteen, Aplenty.
+
+use std::{collections::HashMap, fmt, iter};
+
+use crate::utils::{interval::Interval, parse, Tile};
+
+const FIRST_WORKFLOW: &str = "in";
+
+/// The ratings range from one to this value, both included.
+const MAX_RATING: u64 = 4000;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, Tile)]
+enum Category {
+    #[tile('x')]
+    ExtremelyCoolLooking,
+    #[tile('m')]
+    Musical,
+    #[tile('a')]
+    Aerodynamic,
+    #[tile('s')]
+    Shiny,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, Tile)]
+enum Comparison {
+    #[tile('<')]
+    Less,
+    #[tile('>')]
+    Greater,
+}
+
+/// A part, with its rating in each Category (in the order of `Category::ALL`).
+type Part = [u64; 4];
+
+/// A box of parts, with a range of ratings in each Category.
+type PartBox = [Interval<u64>; 4];
+
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+enum Target {
+    Accept,
+    Reject,
+    Workflow(String),
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+struct Condition {
+    category: Category,
+    comparison: Comparison,
+    value: u64,
+}
+
+impl Condition {
+    /// Return the range of ratings (from one to `MAX_RATING`) that satisfy
+    /// the Condition.
+    pub fn interval(&self) -> Interval<u64> {
+        match self.comparison {
+            Comparison::Less => Interval::new(1, self.value),
+            Comparison::Greater => Interval::new(self.value + 1, MAX_RATING + 1),
+        }
+    }
+
+    pub fn is_satisfied(&self, part: &Part) -> bool {
+        let rating = part[self.category as usize];
+
+        match self.comparison {
+            Comparison::Less => rating < self.value,
+            Comparison::Greater => rating > self.value,
+        }
+    }
+
+    /// Split a box of parts into the parts that satisfy the Condition and
+    /// the ones that do not.
+    pub fn split(&self, part_box: PartBox) -> (PartBox, PartBox) {
+        let i = self.category as usize;
+
+        let (before, after) = part_box[i].difference(&self.interval());
+
+        let mut satisfied = part_box;
+        satisfied[i] = part_box[i].intersection(&self.interval());
+
+        // The ratings that satisfy the Condition are either a prefix or a
+        // suffix of all ratings, so the others are on a single side.
+        let mut unsatisfied = part_box;
+        unsatisfied[i] = match self.comparison {
+            Comparison::Less => after,
+            Comparison::Greater => before,
+        };
+
+        (satisfied, unsatisfied)
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+struct Rule {
+    /// The Condition to send a part to the target; the last Rule of a
+    /// Workflow has none.
+    condition: Option<Condition>,
+    target: Target,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+struct Workflow {
+    name: String,
+    rules: Vec<Rule>,
+}
+
+impl Workflow {
+    /// Return where a part goes after this Workflow.
+    pub fn apply(&self, part: &Part) -> &Target {
+        &self
+            .rules
+            .iter()
+            .find(|rule| {
+                rule.condition
+                    .as_ref()
+                    .map_or(true, |condition| condition.is_satisfied(part))
+            })
+            .expect("the last rule of a workflow should have no condition")
+            .target
+    }
+}
+
+impl fmt::Display for Target {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Target::Accept => write!(f, "A"),
+            Target::Reject => write!(f, "R"),
+            Target::Workflow(name) => write!(f, "{name}"),
+        }
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}{}", self.category, self.comparison, self.value)
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.condition {
+            Some(condition) => write!(f, "{condition}:{}", self.target),
+            None => write!(f, "{}", self.target),
+        }
+    }
+}
+
+impl fmt::Display for Workflow {
+    /// Print the Workflow back in the same format it was parsed.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rules: Vec<String> = self.rules.iter().map(Rule::to_string).collect();
+
+        write!(f, "{}{{{}}}", self.name, rules.join(","))
+    }
+}
+
+type Workflows = HashMap<String, Workflow>;
+
+/// Return the Targets a part goes through, lazily, from the first Workflow
+/// until it is either accepted or rejected.
+fn trace<'a>(workflows: &'a Workflows, part: &'a Part) -> impl Iterator<Item = &'a Target> {
+    let apply = move |name: &str| {
+        workflows
+            .get(name)
+            .unwrap_or_else(|| panic!("there should be a workflow named '{name}'"))
+            .apply(part)
+    };
+
+    iter::successors(Some(apply(FIRST_WORKFLOW)), move |target| match target {
+        Target::Workflow(name) => Some(apply(name)),
+        Target::Accept | Target::Reject => None,
+    })
+}
+
+fn solve1(workflows: &Workflows, parts: &[Part]) -> u64 {
+    parts
+        .iter()
+        .filter(|part| trace(workflows, part).last() == Some(&Target::Accept))
+        .map(|part| part.iter().sum::<u64>())
+        .sum()
+}
+
+/// Count the parts of a box that are accepted from the Workflow `name` on.
+fn count_accepted(workflows: &Workflows, name: &str, part_box: PartBox) -> u64 {
+    let mut rest = part_box;
+    let mut count = 0;
+
+    for rule in &workflows[name].rules {
+        let (sent, unsent) = match &rule.condition {
+            Some(condition) => condition.split(rest),
+            None => (rest, rest.map(|_| Interval::default())),
+        };
+
+        count += match &rule.target {
+            Target::Accept => sent.iter().map(Interval::len).product(),
+            Target::Reject => 0,
+            Target::Workflow(next_name) => count_accepted(workflows, next_name, sent),
+        };
+
+        rest = unsent;
+
+        if rest.iter().any(Interval::is_empty) {
+            break;
+        }
+    }
+
+    count
+}
+
+fn solve2(workflows: &Workflows) -> u64 {
+    let all_parts = [Interval::new(1, MAX_RATING + 1); 4];
+
+    count_accepted(workflows, FIRST_WORKFLOW, all_parts)
+}
+
+fn parse_rule(rule_str: &str) -> Rule {
+    let (condition, target_str) = match rule_str.split_once(':') {
+        Some((condition_str, target_str)) => {
+            let mut chars = condition_str.chars();
+
+            let category = Category::from_char(chars.next().expect("the condition is empty"))
+                .expect("the condition should start with a category");
+
+            let comparison = Comparison::from_char(chars.next().expect("the condition is short"))
+                .expect("the category should be followed by a comparison");
+
+            let value = parse::int(chars.as_str()).expect("the condition should end with a rating");
+
+            let condition = Condition {
+                category,
+                comparison,
+                value,
+            };
+
+            (Some(condition), target_str)
+        }
+        None => (None, rule_str),
+    };
+
+    let target = match target_str {
+        "A" => Target::Accept,
+        "R" => Target::Reject,
+        name => Target::Workflow(name.to_string()),
+    };
+
+    Rule { condition, target }
+}
+
+fn parse_workflow(workflow_str: &str) -> Workflow {
+    let (name, rules_str) = workflow_str
+        .strip_suffix('}')
+        .and_then(|s| s.split_once('{'))
+        .expect("the workflow should have a name followed by rules between braces");
+
+    Workflow {
+        name: name.to_string(),
+        rules: rules_str.split(',').map(parse_rule).collect(),
+    }
+}
+
+fn parse_part(part_str: &str) -> Part {
+    let mut part = [0; 4];
+
+    let ratings_str = part_str
+        .strip_prefix('{')
+        .and_then(|s| s.strip_suffix('}'))
+        .expect("the ratings should be between braces");
+
+    for rating_str in ratings_str.split(',') {
+        let (category_str, _, value_str) =
+            parse::label_op(rating_str, &['=']).expect("each rating should have a category");
+
+        let category = match category_str.chars().collect::<Vec<_>>()[..] {
+            [ch] => Category::from_char(ch).expect("the category should be one of xmas"),
+            _ => panic!("'{category_str}' is not a category"),
+        };
+
+        part[category as usize] = parse::int(value_str).expect("the rating should be an integer");
+    }
+
+    part
+}
+
+fn parse_input(input_data: &str) -> (Workflows, Vec<Part>) {
+    let mut blocks = parse::blocks(input_data);
+
+    let (_, workflows_str) = blocks.next().expect("there should be a list of workflows");
+    let (_, parts_str) = blocks.next().expect("there should be a list of parts");
+
+    let workflows = workflows_str
+        .lines()
+        .map(|line| {
+            let workflow = parse_workflow(line);
+
+            (workflow.name.clone(), workflow)
+        })
+        .collect();
+
+    let parts = parts_str.lines().map(parse_part).collect();
+
+    (workflows, parts)
+}
+
+pub fn day19(input_data: &str) {
+    let (workflows, parts) = parse_input(input_data);
+
+    let sol1 = solve1(&workflows, &parts);
+    let sol2 = solve2(&workflows);
+
+    println!("{sol1}");
+    println!("{sol2}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/day19.test.txt");
+
+    #[test]
+    fn example() {
+        let (workflows, parts) = parse_input(EXAMPLE);
+
+        assert_eq!(solve1(&workflows, &parts), 19114);
+        assert_eq!(solve2(&workflows), 167409079868000);
+    }
+
+    #[test]
+    fn example_trace() {
+        let (workflows, parts) = parse_input(EXAMPLE);
+
+        let path: Vec<String> = trace(&workflows, &parts[0])
+            .map(Target::to_string)
+            .collect();
+
+        assert_eq!(path, ["qqz", "qs", "lnx", "A"]);
+    }
+
+    #[test]
+    fn conditions_outside_rating_range() {
+        let greater = parse_rule("x>10:A").condition.unwrap();
+        let less = parse_rule("x<10:A").condition.unwrap();
+
+        assert!(greater.is_satisfied(&[5000, 0, 0, 0]));
+        assert!(!greater.is_satisfied(&[10, 0, 0, 0]));
+        assert!(less.is_satisfied(&[0, 0, 0, 0]));
+        assert!(!less.is_satisfied(&[10, 0, 0, 0]));
+    }
+
+    #[test]
+    fn workflow_round_trip() {
+        let workflow_str = "px{a<2006:qkq,m>2090:A,rfg}";
+
+        assert_eq!(parse_workflow(workflow_str).to_string(), workflow_str);
+    }
+}
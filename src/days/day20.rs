@@ -0,0 +1,373 @@
+// Copyright (C) 2023 Matheus Fernandes Bigolin <mfrdrbigolin@disroot.org>
+// SPDX-License-Identifier: MIT
+
+// Day Twenty, Pulse Propagation.
+
+use std::collections::{HashMap, VecDeque};
+
+use crate::utils::math;
+
+const BUTTON: &str = "button";
+const BROADCASTER: &str = "broadcaster";
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub enum Pulse {
+    Low,
+    High,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+enum ModuleKind {
+    /// Sends a low pulse to the broadcaster when pushed.
+    Button,
+    /// Repeats every pulse it receives.
+    Broadcaster,
+    /// Ignores high pulses, and flips (sending high if it turns on, and low
+    /// otherwise) when it receives a low pulse.
+    FlipFlop { on: bool },
+    /// Remembers the last pulse from each input, and sends low if all of them
+    /// were high, and high otherwise.
+    Conjunction { memory: Vec<(usize, Pulse)> },
+    /// A module without outputs, which only receives pulses.
+    Untyped,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+struct Module {
+    name: String,
+    kind: ModuleKind,
+    outputs: Vec<usize>,
+}
+
+impl Module {
+    /// Receive a pulse from the module `from`, returning the pulse to send to
+    /// every output, if any.
+    pub fn receive(&mut self, from: usize, pulse: Pulse) -> Option<Pulse> {
+        match &mut self.kind {
+            ModuleKind::Button | ModuleKind::Broadcaster => Some(pulse),
+            ModuleKind::FlipFlop { .. } if pulse == Pulse::High => None,
+            ModuleKind::FlipFlop { on } => {
+                *on = !*on;
+
+                Some(if *on { Pulse::High } else { Pulse::Low })
+            }
+            ModuleKind::Conjunction { memory } => {
+                for (input, last_pulse) in memory.iter_mut() {
+                    if *input == from {
+                        *last_pulse = pulse;
+                    }
+                }
+
+                let all_high = memory.iter().all(|&(_, pulse)| pulse == Pulse::High);
+
+                Some(if all_high { Pulse::Low } else { Pulse::High })
+            }
+            ModuleKind::Untyped => None,
+        }
+    }
+}
+
+/// A pulse sent from a module to another.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub struct PulseEvent {
+    pub from: usize,
+    pub to: usize,
+    pub pulse: Pulse,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Network {
+    modules: Vec<Module>,
+    indices: HashMap<String, usize>,
+}
+
+impl Network {
+    pub fn index(&self, name: &str) -> Option<usize> {
+        self.indices.get(name).copied()
+    }
+
+    /// Push the button, returning an iterator over the pulses it causes, in
+    /// the order they are processed. The pulses are only propagated as the
+    /// iterator is consumed.
+    pub fn press(&mut self) -> Press<'_> {
+        let button = self.index(BUTTON).expect("there should be a button");
+
+        let mut queue = VecDeque::new();
+
+        queue.extend(self.modules[button].outputs.iter().map(|&to| PulseEvent {
+            from: button,
+            to,
+            pulse: Pulse::Low,
+        }));
+
+        Press {
+            network: self,
+            queue,
+        }
+    }
+
+    /// Return the modules that send pulses to the module `to`.
+    pub fn inputs(&self, to: usize) -> impl Iterator<Item = usize> + '_ {
+        self.modules
+            .iter()
+            .enumerate()
+            .filter(move |(_, module)| module.outputs.contains(&to))
+            .map(|(i, _)| i)
+    }
+}
+
+/// The pulses caused by a button press, kept in a FIFO queue.
+pub struct Press<'a> {
+    network: &'a mut Network,
+    queue: VecDeque<PulseEvent>,
+}
+
+impl Iterator for Press<'_> {
+    type Item = PulseEvent;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let event = self.queue.pop_front()?;
+
+        let module = &mut self.network.modules[event.to];
+
+        if let Some(pulse) = module.receive(event.from, event.pulse) {
+            self.queue
+                .extend(module.outputs.iter().map(|&to| PulseEvent {
+                    from: event.to,
+                    to,
+                    pulse,
+                }));
+        }
+
+        Some(event)
+    }
+}
+
+fn solve1(network: &Network, num_presses: usize) -> u64 {
+    let mut network = network.clone();
+
+    let (mut low_count, mut high_count) = (0, 0);
+
+    for _ in 0..num_presses {
+        for event in network.press() {
+            match event.pulse {
+                Pulse::Low => low_count += 1,
+                Pulse::High => high_count += 1,
+            }
+        }
+    }
+
+    low_count * high_count
+}
+
+// The module that receives the pulses of the machine is fed by a single
+// conjunction, which only sends a low pulse when all of its inputs last sent
+// a high one. Each input sends high pulses periodically, so watch them until
+// their periods are known, and find when they all coincide. Return None if
+// the network does not have that shape.
+fn solve2(network: &Network, machine: &str) -> Option<u64> {
+    let mut network = network.clone();
+
+    let machine = network.index(machine)?;
+
+    let [feeder] = network.inputs(machine).collect::<Vec<_>>()[..] else {
+        return None;
+    };
+
+    if !matches!(network.modules[feeder].kind, ModuleKind::Conjunction { .. }) {
+        return None;
+    }
+
+    // The presses in which each input sent a high pulse to the feeder.
+    let mut high_presses: HashMap<usize, Vec<u64>> = network
+        .inputs(feeder)
+        .map(|input| (input, vec![]))
+        .collect();
+
+    let mut presses = 0;
+
+    while high_presses.values().any(|presses| presses.len() < 2) {
+        presses += 1;
+
+        for event in network.press() {
+            if event.to == machine && event.pulse == Pulse::Low {
+                return Some(presses);
+            }
+
+            if event.to == feeder && event.pulse == Pulse::High {
+                let input_presses = high_presses
+                    .get_mut(&event.from)
+                    .expect("the pulse should come from an input of the feeder");
+
+                // An input may send several high pulses in the same press,
+                // which would otherwise give it a period of zero.
+                if input_presses.last() != Some(&presses) {
+                    input_presses.push(presses);
+                }
+            }
+        }
+    }
+
+    let patterns: Vec<(i64, i64)> = high_presses
+        .values()
+        .map(|presses| (presses[0] as i64, (presses[1] - presses[0]) as i64))
+        .collect();
+
+    let first_press = math::earliest_common_arrival(&patterns)?;
+
+    Some(first_press as u64)
+}
+
+pub fn parse_input(input_data: &str) -> Network {
+    // The modules that appear with a type and their outputs, by name.
+    let mut definitions: Vec<(&str, ModuleKind, Vec<&str>)> =
+        vec![(BUTTON, ModuleKind::Button, vec![BROADCASTER])];
+
+    for line in input_data.lines() {
+        let (module_str, outputs_str) = line
+            .split_once(" -> ")
+            .expect("each module should be followed by its outputs");
+
+        let (name, kind) = if let Some(name) = module_str.strip_prefix('%') {
+            (name, ModuleKind::FlipFlop { on: false })
+        } else if let Some(name) = module_str.strip_prefix('&') {
+            (name, ModuleKind::Conjunction { memory: vec![] })
+        } else if module_str == BROADCASTER {
+            (module_str, ModuleKind::Broadcaster)
+        } else {
+            panic!("'{module_str}' is not a flip-flop, a conjunction, or the broadcaster");
+        };
+
+        definitions.push((name, kind, outputs_str.split(", ").collect()));
+    }
+
+    let mut indices: HashMap<String, usize> = HashMap::new();
+    let mut modules = vec![];
+
+    for (name, kind, _) in &definitions {
+        indices.insert(name.to_string(), modules.len());
+
+        modules.push(Module {
+            name: name.to_string(),
+            kind: kind.clone(),
+            outputs: vec![],
+        });
+    }
+
+    for (i, (_, _, outputs)) in definitions.iter().enumerate() {
+        for &output in outputs {
+            let j = *indices.entry(output.to_string()).or_insert_with(|| {
+                modules.push(Module {
+                    name: output.to_string(),
+                    kind: ModuleKind::Untyped,
+                    outputs: vec![],
+                });
+
+                modules.len() - 1
+            });
+
+            modules[i].outputs.push(j);
+
+            // Conjunctions start remembering a low pulse from each input.
+            if let ModuleKind::Conjunction { memory } = &mut modules[j].kind {
+                memory.push((i, Pulse::Low));
+            }
+        }
+    }
+
+    Network { modules, indices }
+}
+
+pub fn day20(input_data: &str) {
+    let network = parse_input(input_data);
+
+    let sol1 = solve1(&network, 1000);
+
+    println!("{sol1}");
+
+    // Only the real input has the machine (“rx”) needed for part two.
+    if let Some(sol2) = solve2(&network, "rx") {
+        println!("{sol2}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/day20.test.txt");
+    const EXAMPLE2: &str = include_str!("../../inputs/day20.test2.txt");
+
+    #[test]
+    fn examples() {
+        assert_eq!(solve1(&parse_input(EXAMPLE), 1000), 32000000);
+        assert_eq!(solve1(&parse_input(EXAMPLE2), 1000), 11687500);
+    }
+
+    #[test]
+    fn first_press_pulses() {
+        let mut network = parse_input(EXAMPLE);
+
+        let pulses: Vec<String> = network
+            .clone()
+            .press()
+            .map(|event| {
+                let pulse = match event.pulse {
+                    Pulse::Low => "low",
+                    Pulse::High => "high",
+                };
+
+                format!(
+                    "{} -{pulse}-> {}",
+                    network.modules[event.from].name, network.modules[event.to].name
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            pulses,
+            [
+                "button -low-> broadcaster",
+                "broadcaster -low-> a",
+                "broadcaster -low-> b",
+                "broadcaster -low-> c",
+                "a -high-> b",
+                "b -high-> c",
+                "c -high-> inv",
+                "inv -low-> a",
+                "a -low-> b",
+                "b -low-> c",
+                "c -low-> inv",
+                "inv -high-> a",
+            ]
+        );
+
+        // Each pulse is only delivered when the iterator yields it.
+        let a = network.index("a").expect("the example should have 'a'");
+        let mut press = network.press();
+
+        press.next();
+        assert_eq!(press.network.modules[a].kind, ModuleKind::FlipFlop { on: false });
+
+        press.next();
+        assert_eq!(press.network.modules[a].kind, ModuleKind::FlipFlop { on: true });
+    }
+
+    #[test]
+    fn machine_without_expected_feeder() {
+        // There is no machine.
+        assert_eq!(solve2(&parse_input(EXAMPLE), "rx"), None);
+
+        // The machine is fed by two modules.
+        let network = parse_input("broadcaster -> a, b\n%a -> rx\n%b -> rx");
+        assert_eq!(solve2(&network, "rx"), None);
+
+        // The machine is fed by a flip-flop.
+        let network = parse_input("broadcaster -> a\n%a -> rx");
+        assert_eq!(solve2(&network, "rx"), None);
+
+        // The feeder has no inputs.
+        let network = parse_input("broadcaster -> a\n%a -> a\n&feeder -> rx");
+        assert_eq!(solve2(&network, "rx"), None);
+    }
+}
@@ -1,16 +1,18 @@
 // Copyright (C) 2023 Matheus Fernandes Bigolin <mfrdrbigolin@disroot.org>
 // SPDX-License-Identifier: MIT
 
+use std::fmt;
+
 // Pull in every day that was created.
 automod::dir!(pub "src/days");
 
 // TODO: Find a better way to do this.
-pub const DAYS: [fn(&str); 16] = [
+pub const DAYS: [fn(&str); 20] = [
     day01::day01,
     day02::day02,
     day03::day03,
     day04::day04,
-    |_| (),
+    day05::day05,
     day06::day06,
     day07::day07,
     day08::day08,
@@ -19,7 +21,56 @@ pub const DAYS: [fn(&str); 16] = [
     day11::day11,
     day12::day12,
     day13::day13,
-    |_| (),
+    day14::day14,
     day15::day15,
     day16::day16,
+    day17::day17,
+    day18::day18,
+    day19::day19,
+    day20::day20,
 ];
+
+/// A problem found in an input by a validator, optionally at a line (starting
+/// from one).
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+pub struct Problem {
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl Problem {
+    pub fn new(line: usize, message: impl Into<String>) -> Self {
+        Problem {
+            line: Some(line),
+            message: message.into(),
+        }
+    }
+
+    pub fn global(message: impl Into<String>) -> Self {
+        Problem {
+            line: None,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {line}: {}", self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// Return the input validator of a day, which checks the format of an input
+/// without solving it and reports every problem found.
+pub fn get_validator(day_number: usize) -> Option<fn(&str) -> Vec<Problem>> {
+    match day_number {
+        7 => Some(day07::validate),
+        8 => Some(day08::validate),
+        10 => Some(day10::validate),
+        13 => Some(day13::validate),
+        _ => None,
+    }
+}
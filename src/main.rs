@@ -5,10 +5,17 @@ mod days;
 mod utils;
 
 fn main() -> std::process::ExitCode {
-    let args: Vec<String> = std::env::args().collect();
+    let mut args: Vec<String> = std::env::args().collect();
+
+    // With `validate`, only check the format of the input instead of solving it.
+    let validate = args.get(1).is_some_and(|arg| arg == "validate");
+
+    if validate {
+        args.remove(1);
+    }
 
     if args.len() <= 1 || args.len() > 3 {
-        eprintln!("Usage: advent <DAY> [<INPUT_EXTENSION>]");
+        eprintln!("Usage: advent [validate] <DAY> [<INPUT_EXTENSION>]");
 
         return std::process::ExitCode::FAILURE;
     }
@@ -84,6 +91,31 @@ fn main() -> std::process::ExitCode {
         return std::process::ExitCode::FAILURE;
     }
 
+    if validate {
+        let validator = match days::get_validator(day_number as usize) {
+            Some(validator) => validator,
+            None => {
+                eprintln!("advent: The day typed does not have a validator.");
+
+                return std::process::ExitCode::FAILURE;
+            }
+        };
+
+        let problems = validator(input_data.as_str());
+
+        if problems.is_empty() {
+            println!("advent: {input_filename} is well-formed.");
+
+            return std::process::ExitCode::SUCCESS;
+        }
+
+        for problem in problems {
+            println!("{input_filename}: {problem}");
+        }
+
+        return std::process::ExitCode::FAILURE;
+    }
+
     days::DAYS[(day_number - 1) as usize](input_data.as_str());
 
     std::process::ExitCode::SUCCESS
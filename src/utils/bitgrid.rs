@@ -0,0 +1,218 @@
+// Copyright (C) 2023 Matheus Fernandes Bigolin <mfrdrbigolin@disroot.org>
+// SPDX-License-Identifier: MIT
+
+// A grid of booleans packed into 64-bit words, for visited sets and two-state
+// terrain. Every row starts at a new word, so that whole rows can be compared
+// (and their differences counted) word by word.
+
+use std::fmt;
+
+use super::{search::Visited, Matrix, Point};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+#[derive(Clone, PartialEq, Eq, Default, Debug, Hash)]
+pub struct BitGrid {
+    words: Vec<u64>,
+    words_per_row: usize,
+    pub rows: usize,
+    pub cols: usize,
+}
+
+impl BitGrid {
+    /// Create a BitGrid with every cell unset.
+    pub fn new(rows: usize, cols: usize) -> Self {
+        let words_per_row = cols.div_ceil(WORD_BITS);
+
+        BitGrid {
+            words: vec![0; rows * words_per_row],
+            words_per_row,
+            rows,
+            cols,
+        }
+    }
+
+    /// Create a BitGrid with the same dimensions of a Matrix, whose set cells
+    /// are the tiles for which `pred` holds.
+    pub fn from_matrix<T>(matrix: &Matrix<T>, mut pred: impl FnMut(&T) -> bool) -> Self {
+        let mut grid = BitGrid::new(matrix.rows, matrix.cols);
+
+        for (point, tile) in matrix.enumerate_points() {
+            if pred(tile) {
+                grid.insert(point);
+            }
+        }
+
+        grid
+    }
+
+    /// Return the word and the bit mask of a point, if it lies inside the grid.
+    fn locate(&self, point: Point) -> Option<(usize, u64)> {
+        let (i, j) = point.to_index(self.rows, self.cols)?;
+
+        Some((i * self.words_per_row + j / WORD_BITS, 1 << (j % WORD_BITS)))
+    }
+
+    /// Verify whether a point lies inside the grid.
+    #[allow(dead_code)]
+    pub fn contains(&self, point: Point) -> bool {
+        point.to_index(self.rows, self.cols).is_some()
+    }
+
+    /// Verify whether the cell of a point is set. Points outside the grid are
+    /// never set.
+    pub fn get(&self, point: Point) -> bool {
+        self.locate(point)
+            .is_some_and(|(word, mask)| self.words[word] & mask != 0)
+    }
+
+    /// Set the cell of a point, returning whether it was previously unset.
+    pub fn insert(&mut self, point: Point) -> bool {
+        let (word, mask) = self
+            .locate(point)
+            .expect("the point should be inside the BitGrid");
+
+        let was_unset = self.words[word] & mask == 0;
+        self.words[word] |= mask;
+
+        was_unset
+    }
+
+    /// Unset the cell of a point, returning whether it was previously set.
+    #[allow(dead_code)]
+    pub fn remove(&mut self, point: Point) -> bool {
+        let (word, mask) = self
+            .locate(point)
+            .expect("the point should be inside the BitGrid");
+
+        let was_set = self.words[word] & mask != 0;
+        self.words[word] &= !mask;
+
+        was_set
+    }
+
+    #[allow(dead_code)]
+    pub fn set(&mut self, point: Point, value: bool) {
+        if value {
+            self.insert(point);
+        } else {
+            self.remove(point);
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// Return the number of set cells.
+    pub fn count(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    fn assert_same_dimensions(&self, other: &BitGrid) {
+        assert!(
+            (self.rows, self.cols) == (other.rows, other.cols),
+            "the BitGrids should have the same dimensions"
+        );
+    }
+
+    /// Set every cell that is set in `other`.
+    pub fn union_with(&mut self, other: &BitGrid) {
+        self.assert_same_dimensions(other);
+
+        for (word, other_word) in self.words.iter_mut().zip(&other.words) {
+            *word |= other_word;
+        }
+    }
+
+    /// Unset every cell that is unset in `other`.
+    #[allow(dead_code)]
+    pub fn intersect_with(&mut self, other: &BitGrid) {
+        self.assert_same_dimensions(other);
+
+        for (word, other_word) in self.words.iter_mut().zip(&other.words) {
+            *word &= other_word;
+        }
+    }
+
+    /// Return the union of many BitGrids with the same dimensions.
+    pub fn union<'a>(grids: impl IntoIterator<Item = &'a BitGrid>) -> Option<BitGrid> {
+        let mut grids = grids.into_iter();
+        let mut union = grids.next()?.clone();
+
+        for grid in grids {
+            union.union_with(grid);
+        }
+
+        Some(union)
+    }
+
+    /// Return the packed words of the i-th row. The bits past the last column
+    /// are always unset.
+    pub fn row_words(&self, i: usize) -> &[u64] {
+        &self.words[i * self.words_per_row..(i + 1) * self.words_per_row]
+    }
+
+    /// Return the number of cells that differ between the i-th and the k-th
+    /// rows.
+    pub fn row_diff(&self, i: usize, k: usize) -> u32 {
+        self.row_words(i)
+            .iter()
+            .zip(self.row_words(k))
+            .map(|(a, b)| (a ^ b).count_ones())
+            .sum()
+    }
+
+    /// Return an iterator over the set points, row by row.
+    #[allow(dead_code)]
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        self.words.iter().enumerate().flat_map(move |(w, &word)| {
+            let (i, j0) = (w / self.words_per_row, (w % self.words_per_row) * WORD_BITS);
+
+            (0..WORD_BITS)
+                .filter(move |bit| word & (1 << bit) != 0)
+                .map(move |bit| Point::from((i, j0 + bit)))
+        })
+    }
+}
+
+impl Visited<Point> for BitGrid {
+    fn is_visited(&self, point: &Point) -> bool {
+        self.get(*point)
+    }
+
+    fn visit(&mut self, point: &Point) -> bool {
+        self.insert(*point)
+    }
+}
+
+impl fmt::Display for BitGrid {
+    /// Print the set cells as '#' and the unset cells as '.'.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for i in 0..self.rows {
+            if i > 0 {
+                writeln!(f)?;
+            }
+
+            for j in 0..self.cols {
+                let ch = if self.get(Point::from((i, j))) {
+                    '#'
+                } else {
+                    '.'
+                };
+                write!(f, "{ch}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,258 @@
+// Copyright (C) 2023 Matheus Fernandes Bigolin <mfrdrbigolin@disroot.org>
+// SPDX-License-Identifier: MIT
+
+// Cycle detection on the sequence x₀, f(x₀), f(f(x₀)), … of the states of a
+// deterministic process. Floyd’s and Brent’s algorithms only keep a couple of
+// states around, while the hash map based detection remembers every state, so
+// that it can answer which state is reached at any step right away.
+
+use std::{collections::HashMap, hash::Hash};
+
+/// The shape of an eventually periodic sequence: after a tail of `start`
+/// states, the states repeat every `len` steps.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub struct Cycle {
+    /// The length of the tail, which is also the step of the first occurrence
+    /// of the first repeated state.
+    pub start: usize,
+    /// The length (period) of the cycle.
+    pub len: usize,
+}
+
+impl Cycle {
+    /// Return the smallest step whose state is the same as the one at step `n`.
+    pub fn equivalent_step(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.len
+        }
+    }
+
+    /// Return the state at step `n`, simulating only up to its equivalent step.
+    #[allow(dead_code)]
+    pub fn state_at<S>(&self, initial: S, mut f: impl FnMut(&S) -> S, n: usize) -> S {
+        (0..self.equivalent_step(n)).fold(initial, |state, _| f(&state))
+    }
+}
+
+/// Find the Cycle by Floyd’s algorithm (the tortoise and the hare).
+#[allow(dead_code)]
+pub fn floyd<S: Clone + Eq>(initial: S, mut f: impl FnMut(&S) -> S) -> Cycle {
+    // Find a step ν that is a multiple of the period, with x_ν = x_2ν.
+    let mut tortoise = f(&initial);
+    let mut hare = f(&tortoise);
+
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        hare = f(&hare);
+    }
+
+    // The first repetition happens when a pointer from x₀ meets one from x_ν.
+    let mut start = 0;
+    tortoise = initial;
+
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        start += 1;
+    }
+
+    let mut len = 1;
+    hare = f(&tortoise);
+
+    while tortoise != hare {
+        hare = f(&hare);
+        len += 1;
+    }
+
+    Cycle { start, len }
+}
+
+/// Find the Cycle by Brent’s algorithm, which usually calls `f` fewer times
+/// than Floyd’s.
+#[allow(dead_code)]
+pub fn brent<S: Clone + Eq>(initial: S, mut f: impl FnMut(&S) -> S) -> Cycle {
+    // Search for the period in windows of increasing powers of two.
+    let mut power = 1;
+    let mut len = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = f(&initial);
+
+    while tortoise != hare {
+        if power == len {
+            tortoise = hare.clone();
+            power *= 2;
+            len = 0;
+        }
+
+        hare = f(&hare);
+        len += 1;
+    }
+
+    // With the hare `len` steps ahead, both meet at the first repetition.
+    let mut start = 0;
+    tortoise = initial.clone();
+    hare = (0..len).fold(initial, |state, _| f(&state));
+
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        start += 1;
+    }
+
+    Cycle { start, len }
+}
+
+/// A record of every state of an eventually periodic sequence up to its
+/// first repetition.
+#[derive(Clone, Debug)]
+pub struct History<S> {
+    pub cycle: Cycle,
+    /// The distinct states, in order; the state at step `start + len` is the
+    /// same as the one at step `start`.
+    pub states: Vec<S>,
+}
+
+impl<S> History<S> {
+    /// Return the state at step `n`, without simulating anything.
+    #[allow(dead_code)]
+    pub fn state_at(&self, n: usize) -> &S {
+        &self.states[self.cycle.equivalent_step(n)]
+    }
+}
+
+/// Find the Cycle by remembering the step in which each state first occurred.
+pub fn find_cycle<S: Clone + Eq + Hash>(initial: S, mut f: impl FnMut(&S) -> S) -> History<S> {
+    let mut seen: HashMap<S, usize> = HashMap::new();
+    let mut states = vec![];
+    let mut state = initial;
+
+    loop {
+        if let Some(&start) = seen.get(&state) {
+            let len = states.len() - start;
+
+            return History {
+                cycle: Cycle { start, len },
+                states,
+            };
+        }
+
+        seen.insert(state.clone(), states.len());
+
+        let next = f(&state);
+        states.push(state);
+        state = next;
+    }
+}
+
+/// Return the state at step `n` (e.g., 1 000 000 000), stopping the
+/// simulation as soon as a state repeats.
+pub fn nth_state<S: Clone + Eq + Hash>(initial: S, mut f: impl FnMut(&S) -> S, n: usize) -> S {
+    let mut seen: HashMap<S, usize> = HashMap::new();
+    let mut states = vec![];
+    let mut state = initial;
+
+    for step in 0..n {
+        if let Some(&start) = seen.get(&state) {
+            let cycle = Cycle {
+                start,
+                len: step - start,
+            };
+
+            return states.swap_remove(cycle.equivalent_step(n));
+        }
+
+        seen.insert(state.clone(), step);
+
+        let next = f(&state);
+        states.push(state);
+        state = next;
+    }
+
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The sequence 0, 1, 2, 3, 4, 5, 6, 3, 4, …, with a tail of three states
+    /// and a cycle of four.
+    fn rho(x: &u32) -> u32 {
+        if *x < 6 {
+            x + 1
+        } else {
+            3
+        }
+    }
+
+    const RHO: Cycle = Cycle { start: 3, len: 4 };
+
+    /// The state at step `n` of `rho`, computed by hand.
+    fn rho_at(n: usize) -> u32 {
+        if n < 3 {
+            n as u32
+        } else {
+            3 + ((n - 3) % 4) as u32
+        }
+    }
+
+    #[test]
+    fn detectors_agree() {
+        assert_eq!(floyd(0, rho), RHO);
+        assert_eq!(brent(0, rho), RHO);
+        assert_eq!(find_cycle(0, rho).cycle, RHO);
+    }
+
+    #[test]
+    fn without_tail() {
+        let cycle = Cycle { start: 0, len: 5 };
+
+        assert_eq!(floyd(0, |x| (x + 1) % 5), cycle);
+        assert_eq!(brent(0, |x| (x + 1) % 5), cycle);
+        assert_eq!(find_cycle(0, |x| (x + 1) % 5).cycle, cycle);
+    }
+
+    #[test]
+    fn fixed_point() {
+        let cycle = Cycle { start: 2, len: 1 };
+
+        assert_eq!(floyd(0, |&x: &u32| (x + 1).min(2)), cycle);
+        assert_eq!(brent(0, |&x: &u32| (x + 1).min(2)), cycle);
+        assert_eq!(find_cycle(0, |&x: &u32| (x + 1).min(2)).cycle, cycle);
+    }
+
+    #[test]
+    fn equivalent_step() {
+        // Before the cycle, every step is its own.
+        assert_eq!(RHO.equivalent_step(0), 0);
+        assert_eq!(RHO.equivalent_step(2), 2);
+        // At the start of the cycle, and after going around it.
+        assert_eq!(RHO.equivalent_step(3), 3);
+        assert_eq!(RHO.equivalent_step(7), 3);
+        assert_eq!(RHO.equivalent_step(8), 4);
+        assert_eq!(RHO.equivalent_step(1_000_000_000), 4);
+    }
+
+    #[test]
+    fn state_at() {
+        let history = find_cycle(0, rho);
+
+        assert_eq!(history.states, vec![0, 1, 2, 3, 4, 5, 6]);
+
+        for n in [0, 2, 3, 6, 7, 10, 1_000_000_000] {
+            assert_eq!(RHO.state_at(0, rho, n), rho_at(n), "step {n}");
+            assert_eq!(*history.state_at(n), rho_at(n), "step {n}");
+        }
+    }
+
+    #[test]
+    fn nth_state() {
+        // Steps before, at, and after the first repetition is found.
+        for n in [0, 1, 3, 6, 7, 8, 11, 1_000_000_000] {
+            assert_eq!(super::nth_state(0, rho, n), rho_at(n), "step {n}");
+        }
+    }
+}
@@ -0,0 +1,200 @@
+// Copyright (C) 2023 Matheus Fernandes Bigolin <mfrdrbigolin@disroot.org>
+// SPDX-License-Identifier: MIT
+
+use std::ops::{Index, IndexMut, Not};
+
+use super::{search::Visited, Point, Tile, Vector};
+
+/// The cardinal Directions (South, North, East, and West). They can be parsed
+/// from arrows (which are also used to print them), from the initials of the
+/// cardinal points, or from the initials of up, down, left, and right.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, Tile)]
+pub enum Direction {
+    #[tile('v', 'S', 'D', '↓')]
+    South,
+    #[tile('^', 'N', 'U', '↑')]
+    North,
+    #[tile('>', 'E', 'R', '→')]
+    East,
+    #[tile('<', 'W', 'L', '←')]
+    West,
+}
+
+impl Not for Direction {
+    type Output = Self;
+
+    /// Return the opposite Direction in a compass rose.
+    fn not(self) -> Self::Output {
+        match self {
+            Direction::South => Direction::North,
+            Direction::North => Direction::South,
+            Direction::East => Direction::West,
+            Direction::West => Direction::East,
+        }
+    }
+}
+
+impl Direction {
+    /// Return the unit Vector pointing towards this Direction.
+    pub fn delta(&self) -> Vector {
+        match self {
+            Direction::South => Vector(1, 0),
+            Direction::North => Vector(-1, 0),
+            Direction::East => Vector(0, 1),
+            Direction::West => Vector(0, -1),
+        }
+    }
+
+    /// Return the Direction after turning 90° counterclockwise.
+    pub fn turn_left(&self) -> Direction {
+        match self {
+            Direction::South => Direction::East,
+            Direction::North => Direction::West,
+            Direction::East => Direction::North,
+            Direction::West => Direction::South,
+        }
+    }
+
+    /// Return the Direction after turning 90° clockwise.
+    pub fn turn_right(&self) -> Direction {
+        !self.turn_left()
+    }
+}
+
+/// The cardinal and intercardinal Directions, in clockwise order from North.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+#[allow(dead_code)]
+pub enum Direction8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+#[allow(dead_code)]
+impl Direction8 {
+    pub const ALL: [Direction8; 8] = [
+        Direction8::North,
+        Direction8::NorthEast,
+        Direction8::East,
+        Direction8::SouthEast,
+        Direction8::South,
+        Direction8::SouthWest,
+        Direction8::West,
+        Direction8::NorthWest,
+    ];
+
+    /// Return the Vector pointing towards this Direction, whose components
+    /// are -1, 0, or 1.
+    pub fn delta(&self) -> Vector {
+        match self {
+            Direction8::North => Vector(-1, 0),
+            Direction8::NorthEast => Vector(-1, 1),
+            Direction8::East => Vector(0, 1),
+            Direction8::SouthEast => Vector(1, 1),
+            Direction8::South => Vector(1, 0),
+            Direction8::SouthWest => Vector(1, -1),
+            Direction8::West => Vector(0, -1),
+            Direction8::NorthWest => Vector(-1, -1),
+        }
+    }
+
+    /// Return the Direction after turning 45° counterclockwise.
+    pub fn turn_left(&self) -> Direction8 {
+        Direction8::ALL[(*self as usize + 7) % 8]
+    }
+
+    /// Return the Direction after turning 45° clockwise.
+    pub fn turn_right(&self) -> Direction8 {
+        Direction8::ALL[(*self as usize + 1) % 8]
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        (*self as usize) % 2 == 1
+    }
+}
+
+impl Not for Direction8 {
+    type Output = Self;
+
+    /// Return the opposite Direction in a compass rose.
+    fn not(self) -> Self::Output {
+        Direction8::ALL[(self as usize + 4) % 8]
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(dir: Direction) -> Self {
+        match dir {
+            Direction::South => Direction8::South,
+            Direction::North => Direction8::North,
+            Direction::East => Direction8::East,
+            Direction::West => Direction8::West,
+        }
+    }
+}
+
+/// A value associated with each cardinal Direction.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug, Hash)]
+pub struct DirectionMap<T> {
+    pub south: T,
+    pub north: T,
+    pub east: T,
+    pub west: T,
+}
+
+impl<T> DirectionMap<T> {
+    /// Create a DirectionMap with the value given by `f` for each Direction.
+    pub fn from_fn(mut f: impl FnMut(Direction) -> T) -> Self {
+        DirectionMap {
+            south: f(Direction::South),
+            north: f(Direction::North),
+            east: f(Direction::East),
+            west: f(Direction::West),
+        }
+    }
+
+    /// Return an iterator over every Direction with its value.
+    pub fn iter(&self) -> impl Iterator<Item = (Direction, &T)> {
+        Direction::ALL.into_iter().map(|dir| (dir, &self[dir]))
+    }
+}
+
+impl<T> Index<Direction> for DirectionMap<T> {
+    type Output = T;
+
+    fn index(&self, dir: Direction) -> &Self::Output {
+        match dir {
+            Direction::South => &self.south,
+            Direction::North => &self.north,
+            Direction::East => &self.east,
+            Direction::West => &self.west,
+        }
+    }
+}
+
+impl<T> IndexMut<Direction> for DirectionMap<T> {
+    fn index_mut(&mut self, dir: Direction) -> &mut Self::Output {
+        match dir {
+            Direction::South => &mut self.south,
+            Direction::North => &mut self.north,
+            Direction::East => &mut self.east,
+            Direction::West => &mut self.west,
+        }
+    }
+}
+
+/// A visited set for each Direction, e.g., to mark beams by heading.
+impl<V: Visited<Point>> Visited<(Direction, Point)> for DirectionMap<V> {
+    fn is_visited(&self, &(dir, point): &(Direction, Point)) -> bool {
+        self[dir].is_visited(&point)
+    }
+
+    fn visit(&mut self, &(dir, point): &(Direction, Point)) -> bool {
+        self[dir].visit(&point)
+    }
+}
@@ -0,0 +1,243 @@
+// Copyright (C) 2023 Matheus Fernandes Bigolin <mfrdrbigolin@disroot.org>
+// SPDX-License-Identifier: MIT
+
+// Measures of simple polygons whose vertices lie on the integer lattice. The
+// vertices are given in order (either clockwise or counterclockwise), and the
+// polygon is implicitly closed, so repeating the first vertex at the end is
+// harmless. Sums are done in i128 so that huge lagoons do not overflow.
+
+use std::{fmt, num::ParseIntError, str::FromStr};
+
+use num::integer::gcd;
+
+use super::{Direction, ParseTileError, Point, Tile};
+
+/// Iterate over the edges of the polygon, including the closing one.
+fn edges(vertices: &[Point]) -> impl Iterator<Item = (Point, Point)> + '_ {
+    vertices
+        .iter()
+        .zip(vertices.iter().cycle().skip(1))
+        .map(|(&p, &q)| (p, q))
+}
+
+/// Return twice the signed area of the polygon by the shoelace formula. It is
+/// positive if the vertices go counterclockwise (with rows growing to the
+/// South and columns to the East), and negative otherwise.
+pub fn double_signed_area(vertices: &[Point]) -> i128 {
+    edges(vertices)
+        .map(|(p, q)| p.0 as i128 * q.1 as i128 - p.1 as i128 * q.0 as i128)
+        .sum()
+}
+
+/// Return the area of the polygon, rounded down if it is not an integer.
+#[allow(dead_code)]
+pub fn area(vertices: &[Point]) -> i128 {
+    double_signed_area(vertices).abs() / 2
+}
+
+/// Return the Euclidean perimeter of the polygon.
+#[allow(dead_code)]
+pub fn perimeter(vertices: &[Point]) -> f64 {
+    edges(vertices)
+        .map(|(p, q)| {
+            let v = q - p;
+            (v.0 as f64).hypot(v.1 as f64)
+        })
+        .sum()
+}
+
+/// Return the number of lattice points on the boundary of the polygon. For
+/// rectilinear polygons, this is also its perimeter.
+pub fn boundary_points(vertices: &[Point]) -> i128 {
+    edges(vertices)
+        .map(|(p, q)| {
+            let v = q - p;
+            gcd(v.0, v.1) as i128
+        })
+        .sum()
+}
+
+/// Return the number of lattice points strictly inside the polygon, by Pick’s
+/// theorem (A = I + B/2 - 1).
+pub fn interior_points(vertices: &[Point]) -> i128 {
+    (double_signed_area(vertices).abs() - boundary_points(vertices)) / 2 + 1
+}
+
+/// Return the number of lattice points inside or on the boundary of the
+/// polygon (e.g., the number of tiles of a dug-out lagoon).
+pub fn lattice_points(vertices: &[Point]) -> i128 {
+    interior_points(vertices) + boundary_points(vertices)
+}
+
+/// A run-length movement instruction, such as "R 6".
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub struct Step {
+    pub dir: Direction,
+    pub len: i64,
+}
+
+impl FromStr for Step {
+    type Err = ParseStepError;
+
+    /// Parse a Step from a Direction and a length, separated by whitespace.
+    /// Anything after the length (e.g., a color) is ignored.
+    fn from_str(step_str: &str) -> Result<Step, Self::Err> {
+        let mut fields = step_str.split_whitespace();
+
+        let (Some(dir_str), Some(len_str)) = (fields.next(), fields.next()) else {
+            return Err(ParseStepError::MissingField);
+        };
+
+        let mut dir_chars = dir_str.chars();
+
+        let dir = match (dir_chars.next(), dir_chars.next()) {
+            (Some(ch), None) => {
+                Direction::from_char(ch).map_err(ParseStepError::InvalidDirection)?
+            }
+            _ => return Err(ParseStepError::MissingField),
+        };
+
+        let len = len_str.parse().map_err(ParseStepError::InvalidLength)?;
+
+        Ok(Step { dir, len })
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ParseStepError {
+    MissingField,
+    InvalidDirection(ParseTileError),
+    InvalidLength(ParseIntError),
+}
+
+impl fmt::Display for ParseStepError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseStepError::MissingField => {
+                write!(f, "the step should have a direction and a length")
+            }
+            ParseStepError::InvalidDirection(error) => write!(f, "{error}"),
+            ParseStepError::InvalidLength(error) => write!(f, "invalid length: {error}"),
+        }
+    }
+}
+
+/// Follow the `steps` from the `start`, returning the vertices of the path
+/// (the start included, and the final point only if it differs from it).
+pub fn trace(start: Point, steps: impl IntoIterator<Item = Step>) -> Vec<Point> {
+    let mut vertices = vec![start];
+    let mut cur = start;
+
+    for Step { dir, len } in steps {
+        cur += dir.delta() * len;
+        vertices.push(cur);
+    }
+
+    if vertices.len() > 1 && vertices.last() == Some(&start) {
+        vertices.pop();
+    }
+
+    vertices
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A unit square, going South, East, North, and West (counterclockwise).
+    const SQUARE: [Point; 4] = [Point(0, 0), Point(1, 0), Point(1, 1), Point(0, 1)];
+
+    /// A right triangle with legs of 3 and 4, counterclockwise.
+    const TRIANGLE: [Point; 3] = [Point(0, 0), Point(3, 0), Point(0, 4)];
+
+    #[test]
+    fn unit_square() {
+        assert_eq!(double_signed_area(&SQUARE), 2);
+        assert_eq!(area(&SQUARE), 1);
+        assert_eq!(perimeter(&SQUARE), 4.0);
+        assert_eq!(boundary_points(&SQUARE), 4);
+        assert_eq!(interior_points(&SQUARE), 0);
+        assert_eq!(lattice_points(&SQUARE), 4);
+    }
+
+    #[test]
+    fn right_triangle() {
+        assert_eq!(area(&TRIANGLE), 6);
+        assert_eq!(perimeter(&TRIANGLE), 12.0);
+        // 3 + 4 on the legs, and only the ends of the hypotenuse.
+        assert_eq!(boundary_points(&TRIANGLE), 8);
+        assert_eq!(interior_points(&TRIANGLE), 3);
+        assert_eq!(lattice_points(&TRIANGLE), 11);
+    }
+
+    #[test]
+    fn repeated_closing_vertex() {
+        let closed = [&TRIANGLE[..], &TRIANGLE[..1]].concat();
+
+        assert_eq!(double_signed_area(&closed), double_signed_area(&TRIANGLE));
+        assert_eq!(boundary_points(&closed), boundary_points(&TRIANGLE));
+        assert_eq!(interior_points(&closed), interior_points(&TRIANGLE));
+        assert_eq!(lattice_points(&closed), lattice_points(&TRIANGLE));
+    }
+
+    #[test]
+    fn winding() {
+        let mut clockwise = TRIANGLE;
+        clockwise.reverse();
+
+        assert_eq!(
+            double_signed_area(&clockwise),
+            -double_signed_area(&TRIANGLE)
+        );
+        assert_eq!(area(&clockwise), area(&TRIANGLE));
+        assert_eq!(interior_points(&clockwise), interior_points(&TRIANGLE));
+        assert_eq!(lattice_points(&clockwise), lattice_points(&TRIANGLE));
+    }
+
+    #[test]
+    fn trace_closed_loop() {
+        let steps = ["R 2", "D 2", "L 2", "U 2"].map(|s| s.parse::<Step>().unwrap());
+
+        let vertices = trace(Point::ORIGIN, steps);
+
+        // The loop ends at the start, which is not repeated.
+        assert_eq!(
+            vertices,
+            vec![Point(0, 0), Point(0, 2), Point(2, 2), Point(2, 0)]
+        );
+        assert!(double_signed_area(&vertices) < 0, "the loop is clockwise");
+        assert_eq!(lattice_points(&vertices), 9);
+    }
+
+    #[test]
+    fn trace_open_path() {
+        let steps = [Step {
+            dir: Direction::South,
+            len: 3,
+        }];
+
+        assert_eq!(trace(Point(1, 1), steps), vec![Point(1, 1), Point(4, 1)]);
+        assert_eq!(trace(Point(1, 1), []), vec![Point(1, 1)]);
+    }
+
+    #[test]
+    fn parse_step() {
+        assert_eq!(
+            "R 6 (#70c710)".parse(),
+            Ok(Step {
+                dir: Direction::East,
+                len: 6
+            })
+        );
+        assert_eq!("R".parse::<Step>(), Err(ParseStepError::MissingField));
+        assert_eq!("RR 6".parse::<Step>(), Err(ParseStepError::MissingField));
+        assert!(matches!(
+            "X 6".parse::<Step>(),
+            Err(ParseStepError::InvalidDirection(_))
+        ));
+        assert!(matches!(
+            "R six".parse::<Step>(),
+            Err(ParseStepError::InvalidLength(_))
+        ));
+    }
+}
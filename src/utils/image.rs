@@ -0,0 +1,245 @@
+// Copyright (C) 2023 Matheus Fernandes Bigolin <mfrdrbigolin@disroot.org>
+// SPDX-License-Identifier: MIT
+
+// Image export of Matrix states, written in formats simple enough to not need
+// an image crate: plain PPM (P3), plain PGM (P2), and SVG.
+
+use std::{
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use super::Matrix;
+
+/// A color in the RGB color space.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+    pub const GRAY: Rgb = Rgb(128, 128, 128);
+    pub const RED: Rgb = Rgb(255, 0, 0);
+    pub const GREEN: Rgb = Rgb(0, 255, 0);
+    pub const BLUE: Rgb = Rgb(0, 0, 255);
+    pub const YELLOW: Rgb = Rgb(255, 255, 0);
+
+    /// Return the luma of this color (ITU-R BT.601), used for grayscale images.
+    pub fn luma(&self) -> u8 {
+        let luma = 299 * self.0 as u32 + 587 * self.1 as u32 + 114 * self.2 as u32;
+
+        (luma / 1000) as u8
+    }
+}
+
+/// The image formats a Matrix can be exported to.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug, Hash)]
+pub enum ImageFormat {
+    #[default]
+    Ppm,
+    Pgm,
+    Svg,
+}
+
+impl ImageFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ImageFormat::Ppm => "ppm",
+            ImageFormat::Pgm => "pgm",
+            ImageFormat::Svg => "svg",
+        }
+    }
+}
+
+impl FromStr for ImageFormat {
+    type Err = ();
+
+    fn from_str(format_str: &str) -> Result<ImageFormat, Self::Err> {
+        match format_str.to_lowercase().as_str() {
+            "ppm" => Ok(ImageFormat::Ppm),
+            "pgm" => Ok(ImageFormat::Pgm),
+            "svg" => Ok(ImageFormat::Svg),
+            _ => Err(()),
+        }
+    }
+}
+
+impl<T> Matrix<T> {
+    /// Write the Matrix as an image in `format`, in which each element is
+    /// drawn as a `scale` × `scale` square colored by `color`.
+    pub fn write_image(
+        &self,
+        out: &mut impl Write,
+        format: ImageFormat,
+        scale: NonZeroUsize,
+        color: impl Fn(&T) -> Rgb,
+    ) -> io::Result<()> {
+        let scale = scale.get();
+
+        let width = self.cols * scale;
+        let height = self.rows * scale;
+
+        match format {
+            ImageFormat::Ppm => writeln!(out, "P3\n{width} {height}\n255")?,
+            ImageFormat::Pgm => writeln!(out, "P2\n{width} {height}\n255")?,
+            ImageFormat::Svg => {
+                writeln!(
+                    out,
+                    "<svg xmlns=\"http://www.w3.org/2000/svg\" \
+                     width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {} {}\" \
+                     shape-rendering=\"crispEdges\">",
+                    self.cols, self.rows
+                )?;
+
+                for i in 0..self.rows {
+                    for j in 0..self.cols {
+                        let Rgb(r, g, b) = color(&self[(i, j)]);
+
+                        writeln!(
+                            out,
+                            "<rect x=\"{j}\" y=\"{i}\" width=\"1\" height=\"1\" \
+                             fill=\"#{r:02x}{g:02x}{b:02x}\"/>"
+                        )?;
+                    }
+                }
+
+                return writeln!(out, "</svg>");
+            }
+        };
+
+        // Plain Netpbm lines should not be longer than 70 characters, so each
+        // pixel goes in its own line.
+        for i in 0..height {
+            for j in 0..width {
+                let pixel = color(&self[(i / scale, j / scale)]);
+
+                match format {
+                    ImageFormat::Ppm => writeln!(out, "{} {} {}", pixel.0, pixel.1, pixel.2)?,
+                    _ => writeln!(out, "{}", pixel.luma())?,
+                };
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Save the Matrix as an image in `path`, whose format is given by the
+    /// file extension.
+    pub fn save_image(
+        &self,
+        path: impl AsRef<Path>,
+        scale: NonZeroUsize,
+        color: impl Fn(&T) -> Rgb,
+    ) -> io::Result<()> {
+        let path = path.as_ref();
+
+        let format = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(|ext| ImageFormat::from_str(ext).ok())
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "the image extension should be ppm, pgm, or svg",
+                )
+            })?;
+
+        let mut out = BufWriter::new(File::create(path)?);
+
+        self.write_image(&mut out, format, scale, color)?;
+
+        out.flush()
+    }
+}
+
+/// A sequence of numbered frames saved into a directory, which solvers can
+/// use to emit their state after each step and later be turned into an
+/// animation.
+#[derive(Debug)]
+pub struct Frames {
+    dir: PathBuf,
+    prefix: String,
+    format: ImageFormat,
+    scale: NonZeroUsize,
+    count: usize,
+}
+
+impl Frames {
+    pub fn new(
+        dir: impl Into<PathBuf>,
+        prefix: &str,
+        format: ImageFormat,
+        scale: NonZeroUsize,
+    ) -> io::Result<Self> {
+        let dir = dir.into();
+
+        fs::create_dir_all(&dir)?;
+
+        Ok(Frames {
+            dir,
+            prefix: prefix.to_owned(),
+            format,
+            scale,
+            count: 0,
+        })
+    }
+
+    /// Create a frame sequence only if the `ADVENT_FRAMES` environment
+    /// variable is set to the output directory. The format and scale can be
+    /// chosen with `ADVENT_FRAMES_FORMAT` (by default, PPM) and
+    /// `ADVENT_FRAMES_SCALE` (by default, one pixel per element).
+    pub fn from_env(prefix: &str) -> Option<Self> {
+        let dir = std::env::var_os("ADVENT_FRAMES")?;
+
+        let format = match std::env::var("ADVENT_FRAMES_FORMAT") {
+            Ok(format_str) => ImageFormat::from_str(&format_str)
+                .expect("ADVENT_FRAMES_FORMAT should be ppm, pgm, or svg"),
+            Err(_) => ImageFormat::default(),
+        };
+
+        let scale = match std::env::var("ADVENT_FRAMES_SCALE") {
+            Ok(scale_str) => scale_str
+                .parse::<NonZeroUsize>()
+                .expect("ADVENT_FRAMES_SCALE should be a positive integer"),
+            Err(_) => NonZeroUsize::MIN,
+        };
+
+        let frames = Frames::new(dir, prefix, format, scale)
+            .expect("the frames directory should be writable");
+
+        Some(frames)
+    }
+
+    /// Save `matrix` as the next frame in the sequence.
+    pub fn push<T>(&mut self, matrix: &Matrix<T>, color: impl Fn(&T) -> Rgb) -> io::Result<()> {
+        let filename = format!(
+            "{}_{:06}.{}",
+            self.prefix,
+            self.count,
+            self.format.extension()
+        );
+
+        let mut out = BufWriter::new(File::create(self.dir.join(filename))?);
+
+        matrix.write_image(&mut out, self.format, self.scale, color)?;
+        out.flush()?;
+
+        self.count += 1;
+
+        Ok(())
+    }
+
+    /// Return the number of frames saved so far.
+    #[allow(dead_code)]
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+}
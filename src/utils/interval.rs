@@ -0,0 +1,438 @@
+// Copyright (C) 2023 Matheus Fernandes Bigolin <mfrdrbigolin@disroot.org>
+// SPDX-License-Identifier: MIT
+
+// Half-open intervals, sets of them, and piecewise offset maps, so that huge
+// ranges of values can be split and transformed without enumerating them.
+
+use std::{
+    fmt,
+    ops::{Add, Sub},
+};
+
+use num::Zero;
+
+/// The half-open Interval [start, end). It is empty if `start >= end`.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Debug, Hash)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Copy + Ord> Interval<T> {
+    pub fn new(start: T, end: T) -> Self {
+        Interval { start, end }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    pub fn contains(&self, x: T) -> bool {
+        self.start <= x && x < self.end
+    }
+
+    pub fn overlaps(&self, other: &Interval<T>) -> bool {
+        !self.intersection(other).is_empty()
+    }
+
+    /// Return the Interval of the values in both Intervals, which may be empty.
+    pub fn intersection(&self, other: &Interval<T>) -> Interval<T> {
+        Interval::new(self.start.max(other.start), self.end.min(other.end))
+    }
+
+    /// Return the parts of this Interval before and after `other`, which may
+    /// be empty.
+    pub fn difference(&self, other: &Interval<T>) -> (Interval<T>, Interval<T>) {
+        (
+            Interval::new(self.start, self.end.min(other.start)),
+            Interval::new(self.start.max(other.end), self.end),
+        )
+    }
+}
+
+impl<T: Copy + Ord + Sub<Output = T> + Zero> Interval<T> {
+    /// Return the number of values in the Interval.
+    pub fn len(&self) -> T {
+        if self.is_empty() {
+            T::zero()
+        } else {
+            self.end - self.start
+        }
+    }
+}
+
+impl<T: Copy + Add<Output = T>> Interval<T> {
+    /// Return the Interval translated by `offset`.
+    pub fn shift(&self, offset: T) -> Interval<T> {
+        Interval {
+            start: self.start + offset,
+            end: self.end + offset,
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Interval<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}, {})", self.start, self.end)
+    }
+}
+
+/// A set of values stored as sorted, disjoint, and non-adjacent non-empty
+/// Intervals.
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet { intervals: vec![] }
+    }
+}
+
+impl<T: Copy + Ord> IntervalSet<T> {
+    pub fn new() -> Self {
+        IntervalSet::default()
+    }
+
+    /// Sort the Intervals and merge the overlapping and adjacent ones.
+    fn normalize(mut intervals: Vec<Interval<T>>) -> Self {
+        intervals.retain(|interval| !interval.is_empty());
+        intervals.sort_unstable();
+
+        let mut merged: Vec<Interval<T>> = Vec::with_capacity(intervals.len());
+
+        for interval in intervals {
+            match merged.last_mut() {
+                Some(last) if interval.start <= last.end => {
+                    last.end = last.end.max(interval.end);
+                }
+                _ => merged.push(interval),
+            }
+        }
+
+        IntervalSet { intervals: merged }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn contains(&self, x: T) -> bool {
+        // The Intervals are sorted, so look only at the last one starting
+        // before (or at) `x`.
+        let i = self
+            .intervals
+            .partition_point(|interval| interval.start <= x);
+
+        i > 0 && self.intervals[i - 1].contains(x)
+    }
+
+    /// Add the values of an Interval to the set.
+    pub fn insert(&mut self, interval: Interval<T>) {
+        let mut intervals = std::mem::take(&mut self.intervals);
+        intervals.push(interval);
+
+        *self = IntervalSet::normalize(intervals);
+    }
+
+    /// Return the (normalized) Intervals of the set, in increasing order.
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Interval<T>> {
+        self.intervals.iter()
+    }
+
+    pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        IntervalSet::normalize([&self.intervals[..], &other.intervals[..]].concat())
+    }
+
+    pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut intervals = vec![];
+        let (mut i, mut k) = (0, 0);
+
+        // Sweep both sorted lists, advancing the Interval that ends first.
+        while i < self.intervals.len() && k < other.intervals.len() {
+            let (a, b) = (self.intervals[i], other.intervals[k]);
+
+            intervals.push(a.intersection(&b));
+
+            if a.end < b.end {
+                i += 1;
+            } else {
+                k += 1;
+            }
+        }
+
+        IntervalSet::normalize(intervals)
+    }
+
+    pub fn difference(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut intervals = vec![];
+
+        for &interval in &self.intervals {
+            let mut rest = interval;
+
+            for removed in other.intervals.iter().filter(|b| b.overlaps(&interval)) {
+                let (before, after) = rest.difference(removed);
+
+                intervals.push(before);
+                rest = after;
+            }
+
+            intervals.push(rest);
+        }
+
+        IntervalSet::normalize(intervals)
+    }
+}
+
+impl<T: Copy + Ord + Sub<Output = T> + Zero> IntervalSet<T> {
+    /// Return the number of values in the set.
+    pub fn len(&self) -> T {
+        self.intervals
+            .iter()
+            .fold(T::zero(), |acc, interval| acc + interval.len())
+    }
+}
+
+impl<T: Copy + Ord> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        IntervalSet::normalize(iter.into_iter().collect())
+    }
+}
+
+impl<T: Copy + Ord> From<Interval<T>> for IntervalSet<T> {
+    fn from(interval: Interval<T>) -> Self {
+        IntervalSet::normalize(vec![interval])
+    }
+}
+
+impl<'a, T> IntoIterator for &'a IntervalSet<T> {
+    type Item = &'a Interval<T>;
+    type IntoIter = std::slice::Iter<'a, Interval<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.intervals.iter()
+    }
+}
+
+/// A piecewise map that adds a fixed offset to the values of each of its
+/// (disjoint) source Intervals, and leaves every other value unchanged.
+#[derive(Clone, PartialEq, Eq, Default, Debug, Hash)]
+pub struct OffsetMap<T> {
+    /// The pieces, sorted by their source Intervals.
+    pieces: Vec<(Interval<T>, T)>,
+}
+
+impl<T: Copy + Ord + Add<Output = T>> OffsetMap<T> {
+    pub fn new() -> Self {
+        OffsetMap { pieces: vec![] }
+    }
+
+    /// Add a piece that maps the values of `source` by adding `offset`.
+    ///
+    /// # Panics
+    ///
+    /// If `source` overlaps the source of another piece.
+    pub fn insert(&mut self, source: Interval<T>, offset: T) {
+        if source.is_empty() {
+            return;
+        }
+
+        let i = self
+            .pieces
+            .partition_point(|(piece, _)| piece.start < source.start);
+
+        let overlaps_prev = i > 0 && self.pieces[i - 1].0.overlaps(&source);
+        let overlaps_next = i < self.pieces.len() && self.pieces[i].0.overlaps(&source);
+
+        assert!(
+            !overlaps_prev && !overlaps_next,
+            "the pieces of an OffsetMap should not overlap"
+        );
+
+        self.pieces.insert(i, (source, offset));
+    }
+
+    #[allow(dead_code)]
+    pub fn pieces(&self) -> &[(Interval<T>, T)] {
+        &self.pieces
+    }
+
+    /// Map a single value.
+    pub fn map(&self, x: T) -> T {
+        let i = self.pieces.partition_point(|(piece, _)| piece.start <= x);
+
+        match i.checked_sub(1).map(|i| self.pieces[i]) {
+            Some((piece, offset)) if piece.contains(x) => x + offset,
+            _ => x,
+        }
+    }
+
+    /// Map all the values of an Interval, splitting it at the boundaries of
+    /// the pieces.
+    pub fn map_interval(&self, interval: Interval<T>) -> IntervalSet<T> {
+        let mut mapped = vec![];
+        let mut rest = interval;
+
+        for &(piece, offset) in &self.pieces {
+            if rest.is_empty() {
+                break;
+            }
+
+            let (before, after) = rest.difference(&piece);
+
+            // The values before the piece are not in any piece, since the
+            // pieces are sorted.
+            mapped.push(before);
+            mapped.push(rest.intersection(&piece).shift(offset));
+
+            rest = after;
+        }
+
+        mapped.push(rest);
+
+        IntervalSet::normalize(mapped)
+    }
+
+    /// Map all the values of an IntervalSet.
+    pub fn map_set(&self, set: &IntervalSet<T>) -> IntervalSet<T> {
+        IntervalSet::normalize(
+            set.iter()
+                .flat_map(|&interval| self.map_interval(interval).intervals)
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+
+    /// A xorshift generator, returning values in `0..bound`.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self, bound: i64) -> i64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+
+            (self.0 % bound as u64) as i64
+        }
+
+        /// A set of up to three random, possibly empty or overlapping,
+        /// Intervals within `0..20`.
+        fn interval_set(&mut self) -> IntervalSet<i64> {
+            (0..self.next(4))
+                .map(|_| Interval::new(self.next(20), self.next(20)))
+                .collect()
+        }
+
+        /// A map of random, non-overlapping pieces within `0..20`, with offsets
+        /// in `-10..10`.
+        fn offset_map(&mut self) -> OffsetMap<i64> {
+            let mut map = OffsetMap::new();
+            let mut cursor = 0;
+
+            for _ in 0..self.next(4) {
+                let start = cursor + self.next(3);
+                let end = start + self.next(5);
+
+                map.insert(Interval::new(start, end), self.next(20) - 10);
+
+                cursor = end;
+            }
+
+            map
+        }
+    }
+
+    fn values(set: &IntervalSet<i64>) -> BTreeSet<i64> {
+        set.iter()
+            .flat_map(|interval| interval.start..interval.end)
+            .collect()
+    }
+
+    fn is_normalized(set: &IntervalSet<i64>) -> bool {
+        set.iter().all(|interval| !interval.is_empty())
+            && set.intervals().windows(2).all(|w| w[0].end < w[1].start)
+    }
+
+    #[test]
+    fn set_operations_match_btreeset() {
+        let mut rng = Rng(0x2023_1205);
+
+        for _ in 0..1000 {
+            let (a, b) = (rng.interval_set(), rng.interval_set());
+            let (a_values, b_values) = (values(&a), values(&b));
+
+            let union = a.union(&b);
+            let intersection = a.intersection(&b);
+            let difference = a.difference(&b);
+
+            assert_eq!(values(&union), &a_values | &b_values);
+            assert_eq!(values(&intersection), &a_values & &b_values);
+            assert_eq!(values(&difference), &a_values - &b_values);
+
+            for set in [&a, &union, &intersection, &difference] {
+                assert!(
+                    is_normalized(set),
+                    "{:?} is not normalized",
+                    set.intervals()
+                );
+            }
+
+            assert_eq!(a.len(), a_values.len() as i64);
+            assert_eq!(a.is_empty(), a_values.is_empty());
+
+            for x in -2..22 {
+                assert_eq!(a.contains(x), a_values.contains(&x));
+            }
+        }
+    }
+
+    #[test]
+    fn offset_map_matches_pointwise_map() {
+        let mut rng = Rng(0x2023_1219);
+
+        for _ in 0..1000 {
+            let map = rng.offset_map();
+            let set = rng.interval_set();
+
+            // Map each value by searching every piece.
+            for x in -2..22 {
+                let expected = map
+                    .pieces()
+                    .iter()
+                    .find(|(piece, _)| piece.contains(x))
+                    .map_or(x, |&(_, offset)| x + offset);
+
+                assert_eq!(map.map(x), expected);
+            }
+
+            let expected: BTreeSet<i64> = values(&set).into_iter().map(|x| map.map(x)).collect();
+
+            let mapped = map.map_set(&set);
+
+            assert_eq!(values(&mapped), expected);
+            assert!(
+                is_normalized(&mapped),
+                "{:?} is not normalized",
+                mapped.intervals()
+            );
+
+            for &interval in &set {
+                let expected: BTreeSet<i64> =
+                    (interval.start..interval.end).map(|x| map.map(x)).collect();
+
+                assert_eq!(values(&map.map_interval(interval)), expected);
+            }
+        }
+    }
+}
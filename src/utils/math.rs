@@ -0,0 +1,222 @@
+// Copyright (C) 2023 Matheus Fernandes Bigolin <mfrdrbigolin@disroot.org>
+// SPDX-License-Identifier: MIT
+
+// Number theory helpers: the extended Euclidean algorithm, modular inverses,
+// and the Chinese Remainder Theorem for moduli that are not necessarily
+// coprime. Intermediate products are computed in i128, so that any i64
+// inputs are safe; results that do not fit in an i64 are None instead.
+
+/// Return (g, x, y) such that ax + by = g = gcd(a, b), with g ≥ 0.
+#[allow(dead_code)]
+pub fn ext_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (g, x, y) = ext_gcd_wide(a as i128, b as i128);
+
+    (g as i64, x as i64, y as i64)
+}
+
+fn ext_gcd_wide(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let q = old_r / r;
+
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r < 0 {
+        (old_r, old_x, old_y) = (-old_r, -old_x, -old_y);
+    }
+
+    (old_r, old_x, old_y)
+}
+
+/// Return the inverse of `a` modulo `m` (in 0..m), if `a` and `m` are coprime.
+#[allow(dead_code)]
+pub fn mod_inverse(a: i64, m: i64) -> Option<i64> {
+    assert!(m > 0, "the modulus should be positive");
+
+    let (g, x, _) = ext_gcd(a.rem_euclid(m), m);
+
+    (g == 1).then(|| x.rem_euclid(m))
+}
+
+/// Solve the system of congruences x ≡ aᵢ (mod mᵢ), returning (x, m) such that
+/// every solution is congruent to x modulo m = lcm(m₁, m₂, …), with x in 0..m.
+/// Return None if the congruences contradict each other (which can only
+/// happen if the moduli are not coprime), or if the lcm does not fit in an i64.
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    let mut solution: (i128, i128) = (0, 1);
+
+    for &(a, m) in congruences {
+        assert!(m > 0, "the moduli should be positive");
+
+        let (x, n) = solution;
+        let (a, m) = ((a as i128).rem_euclid(m as i128), m as i128);
+
+        // Find k such that x + kn ≡ a (mod m), i.e., kn ≡ a - x (mod m).
+        let (g, p, _) = ext_gcd_wide(n, m);
+
+        if (a - x) % g != 0 {
+            return None;
+        }
+
+        // Since n and m fit in an i64, the products below fit in an i128.
+        let lcm = n / g * m;
+
+        if lcm > i64::MAX as i128 {
+            return None;
+        }
+
+        let k = ((a - x) / g * p).rem_euclid(m / g);
+
+        solution = ((x + k * n).rem_euclid(lcm), lcm);
+    }
+
+    let (x, m) = solution;
+
+    // The solution is in 0..m, so both fit in an i64.
+    Some((x as i64, m as i64))
+}
+
+/// Combine two arrival patterns, each being the times `offset + k * period`
+/// for k ≥ 0 (a zero period meaning a single arrival at `offset`), into the
+/// pattern of the times that belong to both. Return None if there is none, or
+/// if it does not fit in an i64.
+pub fn combine_arrivals(
+    (offset1, period1): (i64, i64),
+    (offset2, period2): (i64, i64),
+) -> Option<(i64, i64)> {
+    let arrives_at = |t: i64, offset: i64, period: i64| match period {
+        0 => t == offset,
+        _ => t >= offset && t.abs_diff(offset) % period.unsigned_abs() == 0,
+    };
+
+    match (period1, period2) {
+        (0, _) => arrives_at(offset1, offset2, period2).then_some((offset1, 0)),
+        (_, 0) => arrives_at(offset2, offset1, period1).then_some((offset2, 0)),
+        _ => {
+            let (x, lcm) = crt(&[(offset1, period1), (offset2, period2)])?;
+
+            // The earliest common time must come after both offsets.
+            let latest = offset1.max(offset2) as i128;
+            let first = latest + (x as i128 - latest).rem_euclid(lcm as i128);
+
+            Some((i64::try_from(first).ok()?, lcm))
+        }
+    }
+}
+
+/// Return the earliest time at which every one of the arrival patterns (see
+/// `combine_arrivals`) arrives, if there is any (and it fits in an i64).
+pub fn earliest_common_arrival(patterns: &[(i64, i64)]) -> Option<i64> {
+    let (&first, rest) = patterns.split_first()?;
+
+    let (offset, _) = rest
+        .iter()
+        .try_fold(first, |acc, &pattern| combine_arrivals(acc, pattern))?;
+
+    Some(offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ext_gcd_bezout() {
+        for (a, b) in [
+            (240, 46),
+            (-4, 6),
+            (7, 0),
+            (0, -5),
+            (i64::MAX, i64::MAX - 1),
+        ] {
+            let (g, x, y) = ext_gcd(a, b);
+
+            assert_eq!(g, num::integer::gcd(a, b), "gcd({a}, {b})");
+            assert_eq!(a as i128 * x as i128 + b as i128 * y as i128, g as i128);
+        }
+    }
+
+    #[test]
+    fn mod_inverse() {
+        assert_eq!(super::mod_inverse(3, 7), Some(5));
+        assert_eq!(super::mod_inverse(-3, 7), Some(2));
+        assert_eq!(super::mod_inverse(10, 7), Some(5));
+        assert_eq!(super::mod_inverse(2, 4), None);
+        assert_eq!(super::mod_inverse(5, 1), Some(0));
+    }
+
+    #[test]
+    fn crt_coprime_moduli() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(-1, 5)]), Some((4, 5)));
+        assert_eq!(crt(&[]), Some((0, 1)));
+    }
+
+    #[test]
+    fn crt_non_coprime_moduli() {
+        assert_eq!(crt(&[(1, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt(&[(0, 6), (0, 4), (0, 10)]), Some((0, 60)));
+    }
+
+    #[test]
+    fn crt_contradiction() {
+        assert_eq!(crt(&[(0, 4), (1, 6)]), None);
+        assert_eq!(crt(&[(1, 2), (2, 3), (0, 4)]), None);
+    }
+
+    #[test]
+    fn crt_overflow() {
+        assert_eq!(crt(&[(0, 1 << 40), (1, (1 << 40) - 1)]), None);
+        assert_eq!(crt(&[(1, i64::MAX), (2, i64::MAX - 1)]), None);
+        assert_eq!(crt(&[(5, i64::MAX), (5, i64::MAX)]), Some((5, i64::MAX)));
+    }
+
+    #[test]
+    fn combine_periodic_arrivals() {
+        assert_eq!(combine_arrivals((2, 3), (3, 5)), Some((8, 15)));
+
+        // The first common time comes after both offsets.
+        assert_eq!(combine_arrivals((20, 3), (3, 5)), Some((23, 15)));
+        assert_eq!(combine_arrivals((0, 4), (1, 6)), None);
+    }
+
+    #[test]
+    fn combine_zero_period_arrivals() {
+        assert_eq!(combine_arrivals((7, 0), (1, 2)), Some((7, 0)));
+        assert_eq!(combine_arrivals((1, 2), (8, 0)), None);
+        assert_eq!(combine_arrivals((1, 0), (3, 2)), None);
+        assert_eq!(combine_arrivals((5, 0), (5, 0)), Some((5, 0)));
+        assert_eq!(
+            combine_arrivals((i64::MAX, 0), (i64::MIN, 1)),
+            Some((i64::MAX, 0))
+        );
+    }
+
+    #[test]
+    fn earliest_common_arrival() {
+        assert_eq!(super::earliest_common_arrival(&[]), None);
+        assert_eq!(super::earliest_common_arrival(&[(7, 0)]), Some(7));
+        assert_eq!(
+            super::earliest_common_arrival(&[(3, 3), (4, 4), (5, 5)]),
+            Some(60)
+        );
+        assert_eq!(
+            super::earliest_common_arrival(&[(20, 3), (3, 5), (23, 0)]),
+            Some(23)
+        );
+        assert_eq!(
+            super::earliest_common_arrival(&[(20, 3), (3, 5), (24, 0)]),
+            None
+        );
+        assert_eq!(
+            super::earliest_common_arrival(&[(0, 1 << 40), (1, (1 << 40) - 1)]),
+            None
+        );
+    }
+}
@@ -0,0 +1,544 @@
+// Copyright (C) 2023 Matheus Fernandes Bigolin <mfrdrbigolin@disroot.org>
+// SPDX-License-Identifier: MIT
+
+pub mod bitgrid;
+pub mod cycle;
+pub mod direction;
+pub mod geometry;
+pub mod image;
+pub mod interval;
+pub mod math;
+pub mod parse;
+pub mod point;
+pub mod poly;
+pub mod search;
+pub mod sparse;
+pub mod wrapping;
+
+pub use bitgrid::BitGrid;
+pub use direction::{Direction, DirectionMap};
+pub use point::{Point, Vector};
+pub use sparse::SparseGrid;
+pub use tile_derive::Tile;
+
+use std::{
+    fmt,
+    iter::StepBy,
+    ops::{Index, IndexMut},
+    slice,
+    str::FromStr,
+};
+
+/// All (Δi, Δj) to cover the horizontal and vertical neighbors of a tile.
+pub const VON_NEUMANN: [Vector; 4] = [Vector(1, 0), Vector(-1, 0), Vector(0, 1), Vector(0, -1)];
+
+/// All (Δi, Δj) to cover the horizontal, vertical, and diagonal neighbors of a tile.
+pub const MOORE: [Vector; 8] = [
+    Vector(-1, -1),
+    Vector(-1, 0),
+    Vector(-1, 1),
+    Vector(0, -1),
+    Vector(0, 1),
+    Vector(1, -1),
+    Vector(1, 0),
+    Vector(1, 1),
+];
+
+/// The kinds of neighborhood of a tile: Von Neumann (four orthogonal
+/// neighbors), Moore (eight orthogonal and diagonal neighbors), or a custom
+/// list of (Δi, Δj) offsets.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub enum Neighborhood<'a> {
+    VonNeumann,
+    Moore,
+    Custom(&'a [Vector]),
+}
+
+impl<'a> Neighborhood<'a> {
+    pub fn offsets(&self) -> &'a [Vector] {
+        match *self {
+            Neighborhood::VonNeumann => &VON_NEUMANN,
+            Neighborhood::Moore => &MOORE,
+            Neighborhood::Custom(offsets) => offsets,
+        }
+    }
+}
+
+/// A two-dimensional Matrix.
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+pub struct Matrix<T> {
+    data: Vec<T>,
+    pub rows: usize,
+    pub cols: usize,
+}
+
+impl<T: Default + Clone> Matrix<T> {
+    pub fn new(rows: usize, cols: usize) -> Self {
+        let data = vec![Default::default(); rows * cols];
+
+        Matrix { data, rows, cols }
+    }
+
+    pub fn get_row(&self, i: usize) -> Vec<T> {
+        self.row(i).to_vec()
+    }
+
+    pub fn get_rows(&self) -> Vec<Vec<T>> {
+        self.rows().map(|row| row.to_vec()).collect()
+    }
+
+    pub fn get_col(&self, j: usize) -> Vec<T> {
+        self.col(j).iter().cloned().collect()
+    }
+
+    pub fn get_cols(&self) -> Vec<Vec<T>> {
+        self.cols()
+            .map(|col| col.iter().cloned().collect())
+            .collect()
+    }
+}
+
+impl<T> Matrix<T> {
+    /// Verify whether a point lies inside the Matrix.
+    pub fn contains(&self, point: Point) -> bool {
+        point.to_index(self.rows, self.cols).is_some()
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        let (i, j) = point.to_index(self.rows, self.cols)?;
+
+        Some(&self.data[i * self.cols + j])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        let (i, j) = point.to_index(self.rows, self.cols)?;
+
+        Some(&mut self.data[i * self.cols + j])
+    }
+
+    /// For each Direction, return, in a vector, the neighbor point with its
+    /// associated Direction relative to this point.
+    pub fn get_neighbors(&self, point: Point) -> Vec<(Direction, Point)> {
+        Direction::ALL
+            .into_iter()
+            .map(|dir| (dir, point + dir.delta()))
+            .filter(|&(_, neighbor)| self.contains(neighbor))
+            .collect()
+    }
+
+    /// Return an iterator over the neighbors of a point inside the Matrix in
+    /// a `neighborhood`, with their (Δi, Δj) offsets relative to this point.
+    pub fn neighbors<'a>(
+        &self,
+        point: Point,
+        neighborhood: Neighborhood<'a>,
+    ) -> impl Iterator<Item = (Vector, Point)> + 'a {
+        let (m, n) = (self.rows, self.cols);
+
+        neighborhood
+            .offsets()
+            .iter()
+            .map(move |&offset| (offset, point + offset))
+            .filter(move |&(_, neighbor)| neighbor.to_index(m, n).is_some())
+    }
+
+    /// Return the i-th row of the Matrix, borrowed without copying.
+    pub fn row(&self, i: usize) -> &[T] {
+        &self.data[i * self.cols..(i + 1) * self.cols]
+    }
+
+    pub fn row_mut(&mut self, i: usize) -> &mut [T] {
+        &mut self.data[i * self.cols..(i + 1) * self.cols]
+    }
+
+    /// Return an iterator over the borrowed rows of the Matrix.
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + ExactSizeIterator {
+        (0..self.rows).map(|i| self.row(i))
+    }
+
+    /// Return the j-th column of the Matrix, borrowed without copying.
+    pub fn col(&self, j: usize) -> Col<'_, T> {
+        assert!(j < self.cols, "the column should be inside the Matrix");
+
+        Col {
+            data: self.data.get(j..).unwrap_or(&[]),
+            stride: self.cols,
+            len: self.rows,
+        }
+    }
+
+    /// Return an iterator over the borrowed columns of the Matrix.
+    pub fn cols(&self) -> impl DoubleEndedIterator<Item = Col<'_, T>> + ExactSizeIterator {
+        (0..self.cols).map(|j| self.col(j))
+    }
+
+    /// Return an iterator over the elements of the Matrix in row-major order.
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.data.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> slice::IterMut<'_, T> {
+        self.data.iter_mut()
+    }
+
+    /// Return an iterator over the elements of the Matrix in row-major order,
+    /// with their points.
+    pub fn enumerate_points(&self) -> impl DoubleEndedIterator<Item = (Point, &T)> {
+        let n = self.cols;
+
+        self.data
+            .iter()
+            .enumerate()
+            .map(move |(idx, elem)| (Point::from((idx / n, idx % n)), elem))
+    }
+
+    /// Transpose the Matrix in place. The Matrix should be square.
+    pub fn transpose_in_place(&mut self) {
+        assert_eq!(self.rows, self.cols, "the Matrix should be square");
+
+        let n = self.cols;
+
+        for i in 0..n {
+            for j in (i + 1)..n {
+                self.data.swap(i * n + j, j * n + i);
+            }
+        }
+    }
+
+    /// Rotate the Matrix clockwise in place. The Matrix should be square.
+    pub fn rotate_cw_in_place(&mut self) {
+        self.transpose_in_place();
+        self.flip_horizontal_in_place();
+    }
+
+    /// Rotate the Matrix counterclockwise in place. The Matrix should be square.
+    pub fn rotate_ccw_in_place(&mut self) {
+        self.transpose_in_place();
+        self.flip_vertical_in_place();
+    }
+
+    /// Mirror the Matrix left to right in place.
+    pub fn flip_horizontal_in_place(&mut self) {
+        for i in 0..self.rows {
+            self.row_mut(i).reverse();
+        }
+    }
+
+    /// Mirror the Matrix top to bottom in place.
+    pub fn flip_vertical_in_place(&mut self) {
+        let (m, n) = (self.rows, self.cols);
+
+        for i in 0..(m / 2) {
+            for j in 0..n {
+                self.data.swap(i * n + j, (m - i - 1) * n + j);
+            }
+        }
+    }
+}
+
+impl<T: Clone> Matrix<T> {
+    pub fn transpose(&self) -> Matrix<T> {
+        let data = self.cols().flat_map(|col| col.iter().cloned()).collect();
+
+        Matrix {
+            data,
+            rows: self.cols,
+            cols: self.rows,
+        }
+    }
+
+    pub fn rotate_cw(&self) -> Matrix<T> {
+        let mut matrix = self.transpose();
+        matrix.flip_horizontal_in_place();
+
+        matrix
+    }
+
+    pub fn rotate_ccw(&self) -> Matrix<T> {
+        let mut matrix = self.transpose();
+        matrix.flip_vertical_in_place();
+
+        matrix
+    }
+
+    /// Return the Matrix mirrored left to right.
+    pub fn flip_horizontal(&self) -> Matrix<T> {
+        let mut matrix = self.clone();
+        matrix.flip_horizontal_in_place();
+
+        matrix
+    }
+
+    /// Return the Matrix mirrored top to bottom.
+    pub fn flip_vertical(&self) -> Matrix<T> {
+        let mut matrix = self.clone();
+        matrix.flip_vertical_in_place();
+
+        matrix
+    }
+
+    /// Extract the `rows` × `cols` sub-grid whose top-left corner is `(i, j)`.
+    pub fn sub_grid(&self, (i, j): (usize, usize), rows: usize, cols: usize) -> Matrix<T> {
+        assert!(
+            i + rows <= self.rows && j + cols <= self.cols,
+            "the sub-grid should be inside the Matrix"
+        );
+
+        let data = (i..(i + rows))
+            .flat_map(|row| self.row(row)[j..(j + cols)].iter().cloned())
+            .collect();
+
+        Matrix { data, rows, cols }
+    }
+
+    /// Overwrite the elements of the Matrix with the `sub_grid`, placing its
+    /// top-left corner at `(i, j)`.
+    pub fn insert_sub_grid(&mut self, (i, j): (usize, usize), sub_grid: &Matrix<T>) {
+        assert!(
+            i + sub_grid.rows <= self.rows && j + sub_grid.cols <= self.cols,
+            "the sub-grid should fit inside the Matrix"
+        );
+
+        for (row, sub_row) in sub_grid.rows().enumerate() {
+            self.row_mut(i + row)[j..(j + sub_grid.cols)].clone_from_slice(sub_row);
+        }
+    }
+}
+
+/// A column of a Matrix, borrowed without copying its elements.
+#[derive(Debug)]
+pub struct Col<'a, T> {
+    /// The Matrix data starting at the first element of the column.
+    data: &'a [T],
+    stride: usize,
+    len: usize,
+}
+
+impl<'a, T> Col<'a, T> {
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, i: usize) -> Option<&'a T> {
+        if i < self.len {
+            Some(&self.data[i * self.stride])
+        } else {
+            None
+        }
+    }
+
+    pub fn iter(&self) -> StepBy<slice::Iter<'a, T>> {
+        self.data.iter().step_by(self.stride)
+    }
+
+    pub fn contains(&self, elem: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.iter().any(|other| other == elem)
+    }
+}
+
+impl<T> Clone for Col<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Col<'_, T> {}
+
+impl<T> Index<usize> for Col<'_, T> {
+    type Output = T;
+
+    fn index(&self, i: usize) -> &Self::Output {
+        self.get(i).expect("the index should be inside the column")
+    }
+}
+
+impl<'a, T> IntoIterator for Col<'a, T> {
+    type Item = &'a T;
+    type IntoIter = StepBy<slice::Iter<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: PartialEq> PartialEq<Col<'_, T>> for Col<'_, T> {
+    fn eq(&self, other: &Col<'_, T>) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for Col<'_, T> {}
+
+impl<T: PartialEq> PartialEq<[T]> for Col<'_, T> {
+    fn eq(&self, other: &[T]) -> bool {
+        self.len == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T> Index<(usize, usize)> for Matrix<T> {
+    type Output = T;
+
+    fn index(&self, (i, j): (usize, usize)) -> &Self::Output {
+        assert!(j < self.cols, "the column should be inside the Matrix");
+
+        &self.data[i * self.cols + j]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Matrix<T> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut Self::Output {
+        assert!(j < self.cols, "the column should be inside the Matrix");
+
+        &mut self.data[i * self.cols + j]
+    }
+}
+
+impl<T> Index<Point> for Matrix<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &Self::Output {
+        self.get(point)
+            .expect("the point should be inside the Matrix")
+    }
+}
+
+impl<T> IndexMut<Point> for Matrix<T> {
+    fn index_mut(&mut self, point: Point) -> &mut Self::Output {
+        self.get_mut(point)
+            .expect("the point should be inside the Matrix")
+    }
+}
+
+impl<T: Tile> FromStr for Matrix<T> {
+    type Err = ParseMatrixError;
+
+    fn from_str(matrix_str: &str) -> Result<Matrix<T>, Self::Err> {
+        let cols = matrix_str.lines().next().unwrap_or("").chars().count();
+
+        let mut data = vec![];
+        let mut rows = 0;
+
+        for (i, line) in matrix_str.lines().enumerate() {
+            let len = line.chars().count();
+
+            if len != cols {
+                return Err(ParseMatrixError::RaggedRow { row: i, len, cols });
+            }
+
+            for (j, ch) in line.chars().enumerate() {
+                let tile = T::from_char(ch).map_err(|error| ParseMatrixError::InvalidTile {
+                    point: Point::from((i, j)),
+                    error,
+                })?;
+
+                data.push(tile);
+            }
+
+            rows += 1;
+        }
+
+        Ok(Matrix { data, rows, cols })
+    }
+}
+
+impl<T: Tile> fmt::Display for Matrix<T> {
+    /// Print the Matrix back in the same format it was parsed.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, row) in self.rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+
+            for tile in row {
+                write!(f, "{}", tile.to_char())?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A tile that can be parsed from, and printed back to, a single character.
+pub trait Tile: Sized {
+    fn from_char(ch: char) -> Result<Self, ParseTileError>;
+
+    fn to_char(&self) -> char;
+}
+
+impl Tile for char {
+    fn from_char(ch: char) -> Result<Self, ParseTileError> {
+        Ok(ch)
+    }
+
+    fn to_char(&self) -> char {
+        *self
+    }
+}
+
+/// Decimal digits, such as the heights or the costs of a grid.
+impl Tile for u8 {
+    fn from_char(ch: char) -> Result<Self, ParseTileError> {
+        match ch.to_digit(10) {
+            Some(digit) => Ok(digit as u8),
+            None => Err(ParseTileError {
+                ch,
+                expected: "0123456789",
+            }),
+        }
+    }
+
+    /// Print the tile as a decimal digit. Tiles above nine are printed as '?'.
+    fn to_char(&self) -> char {
+        char::from_digit(u32::from(*self), 10).unwrap_or('?')
+    }
+}
+
+/// Error returned when parsing a character that is not a valid tile.
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+pub struct ParseTileError {
+    pub ch: char,
+    /// The valid tile symbols.
+    pub expected: &'static str,
+}
+
+impl fmt::Display for ParseTileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "'{}' is not a valid tile (expected one of {})",
+            self.ch, self.expected
+        )
+    }
+}
+
+/// Error returned when parsing an ill-formed Matrix.
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+pub enum ParseMatrixError {
+    InvalidTile { point: Point, error: ParseTileError },
+    RaggedRow { row: usize, len: usize, cols: usize },
+}
+
+impl fmt::Display for ParseMatrixError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseMatrixError::InvalidTile {
+                point: Point(i, j),
+                error,
+            } => {
+                write!(f, "line {}, column {}: {error}", i + 1, j + 1)
+            }
+            ParseMatrixError::RaggedRow { row, len, cols } => write!(
+                f,
+                "line {}: the row has {len} tiles, but the first row has {cols}",
+                row + 1
+            ),
+        }
+    }
+}
@@ -0,0 +1,290 @@
+// Copyright (C) 2023 Matheus Fernandes Bigolin <mfrdrbigolin@disroot.org>
+// SPDX-License-Identifier: MIT
+
+// Helpers for the input shapes that keep coming back: lists of integers,
+// `key: values` lines, blocks separated by blank lines, and `label=value`
+// tokens. Errors carry the (0-based) column, in characters, of the offending
+// text, relative to the string that was given.
+
+use std::{fmt, str::FromStr};
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ParseError {
+    /// The line of the error, if known by whoever parsed the line.
+    pub line: Option<usize>,
+    pub col: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    fn new(col: usize, message: impl Into<String>) -> Self {
+        ParseError {
+            line: None,
+            col,
+            message: message.into(),
+        }
+    }
+
+    /// Attach the (0-based) line in which the error happened.
+    #[allow(dead_code)]
+    pub fn on_line(self, line: usize) -> Self {
+        ParseError {
+            line: Some(line),
+            ..self
+        }
+    }
+
+    /// Move the error `cols` columns to the right, for errors found in a
+    /// substring that starts at that column.
+    pub fn shift(self, cols: usize) -> Self {
+        ParseError {
+            col: self.col + cols,
+            ..self
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    /// Print the error with its position, starting at line and column one.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {}, column {}: ", line + 1, self.col + 1)?,
+            None => write!(f, "column {}: ", self.col + 1)?,
+        }
+
+        write!(f, "{}", self.message)
+    }
+}
+
+/// Return the column, in characters, in which `sub` starts inside `s`.
+///
+/// # Panics
+///
+/// If `sub` is not a substring (i.e., a subslice) of `s`.
+pub fn column(s: &str, sub: &str) -> usize {
+    let byte_offset = (sub.as_ptr() as usize)
+        .checked_sub(s.as_ptr() as usize)
+        .filter(|&offset| offset + sub.len() <= s.len())
+        .expect("the substring should be a slice of the string");
+
+    s[..byte_offset].chars().count()
+}
+
+/// Parse a single integer (either signed or unsigned, depending on `T`),
+/// ignoring the surrounding whitespace.
+pub fn int<T>(s: &str) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    let trimmed = s.trim();
+
+    trimmed
+        .parse()
+        .map_err(|err| ParseError::new(column(s, trimmed), format!("'{trimmed}': {err}")))
+}
+
+/// Parse a whitespace-separated list of integers.
+pub fn ints<T>(s: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    s.split_whitespace()
+        .map(|n| int(n).map_err(|err| err.shift(column(s, n))))
+        .collect()
+}
+
+/// Extract every integer of a string, whatever separates them. A '-' right
+/// before a number is its sign, unless it follows a letter or a digit (as in
+/// "a-1" or "1-2").
+#[allow(dead_code)]
+pub fn all_ints<T>(s: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    let chars: Vec<(usize, char)> = s.char_indices().collect();
+
+    let mut nums = vec![];
+    let mut k = 0;
+
+    while k < chars.len() {
+        if !chars[k].1.is_ascii_digit() {
+            k += 1;
+            continue;
+        }
+
+        let is_negative =
+            k > 0 && chars[k - 1].1 == '-' && (k < 2 || !chars[k - 2].1.is_alphanumeric());
+
+        let first = if is_negative { k - 1 } else { k };
+
+        while k < chars.len() && chars[k].1.is_ascii_digit() {
+            k += 1;
+        }
+
+        let start = chars[first].0;
+        let end = chars.get(k).map_or(s.len(), |&(i, _)| i);
+
+        nums.push(int(&s[start..end]).map_err(|err| err.shift(first))?);
+    }
+
+    Ok(nums)
+}
+
+/// Split a `key: values` line at its first colon, returning the key and the
+/// values, both without surrounding whitespace.
+pub fn key_value(line: &str) -> Result<(&str, &str), ParseError> {
+    let (key, value) = line
+        .split_once(':')
+        .ok_or_else(|| ParseError::new(line.chars().count(), "expected ':' after the key"))?;
+
+    Ok((key.trim(), value.trim()))
+}
+
+/// Parse a `key: n₁ n₂ …` line, returning the key and its integers.
+pub fn key_ints<T>(line: &str) -> Result<(&str, Vec<T>), ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    let (key, value) = key_value(line)?;
+
+    let nums = ints(value).map_err(|err| err.shift(column(line, value)))?;
+
+    Ok((key, nums))
+}
+
+/// Split a token at the first of the `ops` characters (e.g., "rn=1" or "cm-"
+/// with '=' and '-'), returning the label, the operator, and the rest.
+pub fn label_op<'a>(token: &'a str, ops: &[char]) -> Result<(&'a str, char, &'a str), ParseError> {
+    let (i, op) = token
+        .char_indices()
+        .find(|(_, ch)| ops.contains(ch))
+        .ok_or_else(|| {
+            let expected: String = ops.iter().collect();
+
+            ParseError::new(
+                token.chars().count(),
+                format!("expected one of '{expected}' after the label"),
+            )
+        })?;
+
+    Ok((&token[..i], op, &token[i + op.len_utf8()..]))
+}
+
+/// Split the input into blocks separated by blank lines, returning each block
+/// with the (0-based) line in which it starts.
+pub fn blocks(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut line = 0;
+
+    input.split("\n\n").map(move |block| {
+        let start = line;
+        line += block.lines().count() + 1;
+
+        (start, block)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn int_trims_whitespace() {
+        assert_eq!(int::<u32>("  42 "), Ok(42));
+        assert_eq!(int::<i32>(" -7"), Ok(-7));
+    }
+
+    #[test]
+    fn int_error_column() {
+        let err = int::<u32>("  x1").unwrap_err();
+
+        assert_eq!(err.col, 2);
+        assert_eq!(err.message, "'x1': invalid digit found in string");
+        assert!(int::<u32>("-7").is_err());
+    }
+
+    #[test]
+    fn ints_are_whitespace_separated() {
+        assert_eq!(ints::<i64>("1 2  -3\t4"), Ok(vec![1, 2, -3, 4]));
+        assert_eq!(ints::<i64>(""), Ok(vec![]));
+    }
+
+    #[test]
+    fn ints_error_column() {
+        assert_eq!(ints::<i64>("1 2 x 4").unwrap_err().col, 4);
+        assert_eq!(ints::<i64>("  1 2x").unwrap_err().col, 4);
+    }
+
+    #[test]
+    fn all_ints_sign_rule() {
+        assert_eq!(all_ints::<i64>("x=-3, y=4..-5"), Ok(vec![-3, 4, -5]));
+        assert_eq!(all_ints::<i64>("-1 --2"), Ok(vec![-1, -2]));
+
+        // A '-' after a letter or a digit is a separator.
+        assert_eq!(all_ints::<i64>("a-1 1-2"), Ok(vec![1, 1, 2]));
+        assert_eq!(all_ints::<i64>("no numbers"), Ok(vec![]));
+    }
+
+    #[test]
+    fn all_ints_error_column() {
+        assert_eq!(all_ints::<u8>("a 300").unwrap_err().col, 2);
+
+        // The sign is part of the number, so the error starts at it.
+        assert_eq!(all_ints::<u32>("é, -3").unwrap_err().col, 3);
+    }
+
+    #[test]
+    fn key_ints_splits_at_colon() {
+        assert_eq!(
+            key_ints::<u32>("Time:      7  15   30"),
+            Ok(("Time", vec![7, 15, 30]))
+        );
+        assert_eq!(key_ints::<u32>("Card 1:"), Ok(("Card 1", vec![])));
+    }
+
+    #[test]
+    fn key_ints_error_column() {
+        assert_eq!(key_ints::<u32>("Seeds: 1 x").unwrap_err().col, 9);
+
+        // Columns count characters, not bytes.
+        assert_eq!(key_ints::<u32>("Sé: 1 x").unwrap_err().col, 6);
+
+        let err = key_ints::<u32>("no colon").unwrap_err();
+
+        assert_eq!(err.col, 8);
+        assert_eq!(err.message, "expected ':' after the key");
+    }
+
+    #[test]
+    fn label_op_splits_at_first_op() {
+        assert_eq!(label_op("rn=1", &['=', '-']), Ok(("rn", '=', "1")));
+        assert_eq!(label_op("cm-", &['=', '-']), Ok(("cm", '-', "")));
+        assert_eq!(label_op("a=b=c", &['=']), Ok(("a", '=', "b=c")));
+    }
+
+    #[test]
+    fn label_op_error_column() {
+        let err = label_op("abc", &['=', '-']).unwrap_err();
+
+        assert_eq!(err.col, 3);
+        assert_eq!(err.message, "expected one of '=-' after the label");
+    }
+
+    #[test]
+    fn blocks_with_start_lines() {
+        let blocks: Vec<_> = blocks("a\nb\n\nc\n\nd\ne\nf\n").collect();
+
+        assert_eq!(blocks, vec![(0, "a\nb"), (3, "c"), (5, "d\ne\nf\n")]);
+    }
+
+    #[test]
+    fn error_display() {
+        let err = ParseError::new(4, "bad");
+
+        assert_eq!(err.to_string(), "column 5: bad");
+        assert_eq!(err.shift(2).on_line(2).to_string(), "line 3, column 7: bad");
+    }
+}
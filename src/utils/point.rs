@@ -0,0 +1,140 @@
+// Copyright (C) 2023 Matheus Fernandes Bigolin <mfrdrbigolin@disroot.org>
+// SPDX-License-Identifier: MIT
+
+// Signed coordinates, so that points can lie outside of a Matrix (e.g., when
+// walking off-grid or on an infinite plane) without casting back and forth.
+
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A Point in the Cartesian plane, with (row, column) coordinates; rows grow
+/// to the South and columns grow to the East.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Debug, Hash)]
+pub struct Point(pub i64, pub i64);
+
+/// A displacement between two Points, with (Δrow, Δcolumn) components.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Debug, Hash)]
+pub struct Vector(pub i64, pub i64);
+
+impl Point {
+    pub const ORIGIN: Point = Point(0, 0);
+
+    pub fn manhattan(&self, other: Point) -> i64 {
+        (other - *self).manhattan()
+    }
+
+    pub fn chebyshev(&self, other: Point) -> i64 {
+        (other - *self).chebyshev()
+    }
+
+    /// Convert the Point into the (row, column) indices of a `rows` × `cols`
+    /// Matrix, if the Point lies inside it.
+    pub fn to_index(self, rows: usize, cols: usize) -> Option<(usize, usize)> {
+        let i = usize::try_from(self.0).ok().filter(|&i| i < rows)?;
+        let j = usize::try_from(self.1).ok().filter(|&j| j < cols)?;
+
+        Some((i, j))
+    }
+}
+
+impl Vector {
+    pub const ZERO: Vector = Vector(0, 0);
+
+    /// Return the Manhattan (taxicab) norm of the Vector.
+    pub fn manhattan(&self) -> i64 {
+        self.0.abs() + self.1.abs()
+    }
+
+    /// Return the Chebyshev (chessboard) norm of the Vector.
+    pub fn chebyshev(&self) -> i64 {
+        self.0.abs().max(self.1.abs())
+    }
+}
+
+impl From<(usize, usize)> for Point {
+    fn from((i, j): (usize, usize)) -> Self {
+        Point(
+            i64::try_from(i).expect("the row should fit in an i64"),
+            i64::try_from(j).expect("the column should fit in an i64"),
+        )
+    }
+}
+
+impl Add<Vector> for Point {
+    type Output = Point;
+
+    fn add(self, v: Vector) -> Self::Output {
+        Point(self.0 + v.0, self.1 + v.1)
+    }
+}
+
+impl AddAssign<Vector> for Point {
+    fn add_assign(&mut self, v: Vector) {
+        *self = *self + v;
+    }
+}
+
+impl Sub<Vector> for Point {
+    type Output = Point;
+
+    fn sub(self, v: Vector) -> Self::Output {
+        Point(self.0 - v.0, self.1 - v.1)
+    }
+}
+
+impl SubAssign<Vector> for Point {
+    fn sub_assign(&mut self, v: Vector) {
+        *self = *self - v;
+    }
+}
+
+impl Sub for Point {
+    type Output = Vector;
+
+    fn sub(self, other: Point) -> Self::Output {
+        Vector(self.0 - other.0, self.1 - other.1)
+    }
+}
+
+impl Add for Vector {
+    type Output = Vector;
+
+    fn add(self, other: Vector) -> Self::Output {
+        Vector(self.0 + other.0, self.1 + other.1)
+    }
+}
+
+impl AddAssign for Vector {
+    fn add_assign(&mut self, other: Vector) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Vector {
+    type Output = Vector;
+
+    fn sub(self, other: Vector) -> Self::Output {
+        Vector(self.0 - other.0, self.1 - other.1)
+    }
+}
+
+impl SubAssign for Vector {
+    fn sub_assign(&mut self, other: Vector) {
+        *self = *self - other;
+    }
+}
+
+impl Mul<i64> for Vector {
+    type Output = Vector;
+
+    fn mul(self, k: i64) -> Self::Output {
+        Vector(self.0 * k, self.1 * k)
+    }
+}
+
+impl Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Self::Output {
+        Vector(-self.0, -self.1)
+    }
+}
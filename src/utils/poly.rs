@@ -0,0 +1,191 @@
+// Copyright (C) 2023 Matheus Fernandes Bigolin <mfrdrbigolin@disroot.org>
+// SPDX-License-Identifier: MIT
+
+// Exact fitting and extrapolation of integer sequences that are given by a
+// polynomial, either through Newton’s forward differences (for values at
+// consecutive points) or through Lagrange interpolation (for arbitrary
+// points). Every computation is done in integers (or rationals), never in
+// floating point.
+
+use num::rational::Ratio;
+
+/// Return the table of forward differences of the values: the values
+/// themselves, their differences, the differences of those, and so on,
+/// until a row with a single value.
+pub fn differences(values: &[i64]) -> Vec<Vec<i64>> {
+    let mut table = vec![values.to_vec()];
+
+    while table.last().is_some_and(|row| row.len() > 1) {
+        let row = table.last().expect("the table should not be empty");
+        let diffs = row.windows(2).map(|pair| pair[1] - pair[0]).collect();
+
+        table.push(diffs);
+    }
+
+    table
+}
+
+/// Return the degree of the polynomial that generates the values, that is,
+/// the first row of differences that is constant. Since any n values fit a
+/// polynomial of degree n - 1, the constant row should have at least two
+/// values to count; otherwise, the values are not (provably) polynomial and
+/// None is returned. Constant zero values have degree zero.
+pub fn degree(values: &[i64]) -> Option<usize> {
+    differences(values)
+        .iter()
+        .position(|row| row.len() >= 2 && row.windows(2).all(|pair| pair[0] == pair[1]))
+}
+
+/// Return the value at `x` of the polynomial that generates the values, in
+/// which the values are at x = 0, 1, …, n - 1. `x` can be anywhere, including
+/// before the first value (negative x). If the degree is not known (see
+/// `degree`), the whole table of differences is used, as in the polynomial of
+/// degree n - 1 through the values. Return None if there are no values, or if
+/// the value does not fit in an i64.
+pub fn value_at(values: &[i64], x: i64) -> Option<i64> {
+    let degree = degree(values).or(values.len().checked_sub(1))?;
+
+    // By Newton’s forward formula, f(x) = Σⱼ Δʲf(0) C(x, j).
+    let mut value: i128 = 0;
+    let mut binomial: i128 = 1;
+
+    for (j, row) in differences(values).iter().take(degree + 1).enumerate() {
+        if j > 0 {
+            // C(x, j) = C(x, j - 1) (x - j + 1) / j, which is always exact.
+            binomial = binomial.checked_mul(x as i128 - j as i128 + 1)? / j as i128;
+        }
+
+        value = value.checked_add((row[0] as i128).checked_mul(binomial)?)?;
+    }
+
+    i64::try_from(value).ok()
+}
+
+/// Extrapolate the values `k` steps forward (or backward, if `k` is
+/// negative) from the last value; e.g., k = 1 is the next value, and
+/// k = -n is the value right before the first one.
+#[allow(dead_code)]
+pub fn extrapolate(values: &[i64], k: i64) -> Option<i64> {
+    value_at(values, values.len() as i64 - 1 + k)
+}
+
+/// Return the value at `x` of the polynomial of least degree through the
+/// `points` (with distinct x coordinates), by Lagrange interpolation. Return
+/// None if the value is not an integer.
+#[allow(dead_code)]
+pub fn lagrange(points: &[(i64, i64)], x: i64) -> Option<i64> {
+    let mut value = Ratio::from_integer(0i128);
+
+    for (i, &(xi, yi)) in points.iter().enumerate() {
+        let mut term = Ratio::from_integer(yi as i128);
+
+        for (k, &(xk, _)) in points.iter().enumerate() {
+            if i != k {
+                assert!(
+                    xi != xk,
+                    "the x coordinates of the points should be distinct"
+                );
+
+                term *= Ratio::new(x as i128 - xk as i128, xi as i128 - xk as i128);
+            }
+        }
+
+        value += term;
+    }
+
+    if value.is_integer() {
+        i64::try_from(value.to_integer()).ok()
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The triangular numbers, f(x) = (x + 1)(x + 2) / 2, at x = 0, 1, 2, 3.
+    const TRIANGULAR: [i64; 4] = [1, 3, 6, 10];
+
+    /// The powers of two, which are not polynomial.
+    const POWERS: [i64; 5] = [1, 2, 4, 8, 16];
+
+    #[test]
+    fn differences() {
+        assert_eq!(
+            super::differences(&TRIANGULAR),
+            vec![vec![1, 3, 6, 10], vec![2, 3, 4], vec![1, 1], vec![0]]
+        );
+        assert_eq!(super::differences(&[]), vec![vec![]]);
+    }
+
+    #[test]
+    fn degree() {
+        assert_eq!(super::degree(&TRIANGULAR), Some(2));
+        assert_eq!(super::degree(&[5, 5, 5]), Some(0));
+        assert_eq!(super::degree(&[0, 0]), Some(0));
+
+        // The constant row needs at least two values.
+        assert_eq!(super::degree(&[1, 3]), None);
+        assert_eq!(super::degree(&[5]), None);
+        assert_eq!(super::degree(&POWERS), None);
+    }
+
+    #[test]
+    fn value_at_polynomial() {
+        assert_eq!(value_at(&TRIANGULAR, 4), Some(15));
+        assert_eq!(value_at(&TRIANGULAR, 100), Some(5151));
+
+        // Before the first value.
+        assert_eq!(value_at(&TRIANGULAR, -1), Some(0));
+        assert_eq!(value_at(&TRIANGULAR, -2), Some(0));
+        assert_eq!(value_at(&TRIANGULAR, -5), Some(6));
+    }
+
+    #[test]
+    fn value_at_non_polynomial() {
+        // The polynomial of degree four through the values: Σⱼ C(x, j).
+        assert_eq!(value_at(&POWERS, 5), Some(31));
+        assert_eq!(value_at(&POWERS, -1), Some(1));
+
+        assert_eq!(value_at(&[1, 3], 2), Some(5));
+        assert_eq!(value_at(&[5], -1), Some(5));
+        assert_eq!(value_at(&[], 0), None);
+    }
+
+    #[test]
+    fn value_at_overflow() {
+        assert_eq!(value_at(&[0, 1], i64::MAX), Some(i64::MAX));
+        assert_eq!(value_at(&[0, 2], i64::MAX), None);
+        assert_eq!(value_at(&[0, 1, 8, 27], 1 << 62), None);
+        assert_eq!(value_at(&[0, 1, 8, 27], -(1 << 62)), None);
+    }
+
+    #[test]
+    fn extrapolate() {
+        assert_eq!(super::extrapolate(&TRIANGULAR, 1), Some(15));
+        assert_eq!(super::extrapolate(&TRIANGULAR, 0), Some(10));
+        assert_eq!(super::extrapolate(&TRIANGULAR, -4), Some(0));
+        assert_eq!(super::extrapolate(&POWERS, 1), Some(31));
+    }
+
+    #[test]
+    fn lagrange() {
+        let points = [(0, 1), (1, 3), (2, 6)];
+
+        assert_eq!(super::lagrange(&points, 3), Some(10));
+        assert_eq!(super::lagrange(&points, -1), Some(0));
+
+        // The points do not need to be consecutive, nor sorted.
+        assert_eq!(super::lagrange(&[(1, 1), (3, 9), (-2, 4)], 5), Some(25));
+
+        // The line through (0, 0) and (2, 1) is at 1/2 when x = 1.
+        assert_eq!(super::lagrange(&[(0, 0), (2, 1)], 1), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "distinct")]
+    fn lagrange_repeated_x() {
+        super::lagrange(&[(1, 1), (1, 2)], 0);
+    }
+}
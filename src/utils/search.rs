@@ -0,0 +1,600 @@
+// Copyright (C) 2023 Matheus Fernandes Bigolin <mfrdrbigolin@disroot.org>
+// SPDX-License-Identifier: MIT
+
+// Generic graph searches (BFS, DFS, Dijkstra, and A*) over any hashable state,
+// whose edges are given by a `neighbors` closure. Every search starts from
+// possibly many sources, stops early when a goal state is visited, and keeps
+// the predecessor of each state so that paths can be reconstructed.
+
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+use num::Zero;
+
+/// The result of a search.
+#[derive(Clone, Debug)]
+pub struct Search<S, C> {
+    /// The states in the order they were visited.
+    pub order: Vec<S>,
+    /// The goal state that ended the search, if any was found.
+    pub goal: Option<S>,
+    /// The predecessor and the cost of each visited state.
+    parents: HashMap<S, (Option<S>, C)>,
+}
+
+impl<S: Clone + Eq + Hash, C: Copy> Search<S, C> {
+    fn new() -> Self {
+        Search {
+            order: vec![],
+            goal: None,
+            parents: HashMap::new(),
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+
+    pub fn contains(&self, state: &S) -> bool {
+        self.parents.contains_key(state)
+    }
+
+    /// Return the cost to reach a state from the sources (for BFS and DFS,
+    /// the number of steps), if it was visited.
+    pub fn cost(&self, state: &S) -> Option<C> {
+        self.parents.get(state).map(|&(_, cost)| cost)
+    }
+
+    #[allow(dead_code)]
+    pub fn predecessor(&self, state: &S) -> Option<&S> {
+        self.parents.get(state)?.0.as_ref()
+    }
+
+    /// Return the path from a source to the `state`, both included.
+    #[allow(dead_code)]
+    pub fn path(&self, state: &S) -> Option<Vec<S>> {
+        let mut path = vec![state.clone()];
+
+        let mut cur = self.parents.get(state)?;
+
+        while let (Some(prev), _) = cur {
+            path.push(prev.clone());
+            cur = &self.parents[prev];
+        }
+
+        path.reverse();
+
+        Some(path)
+    }
+
+    pub fn goal_cost(&self) -> Option<C> {
+        self.cost(self.goal.as_ref()?)
+    }
+
+    #[allow(dead_code)]
+    pub fn goal_path(&self) -> Option<Vec<S>> {
+        self.path(self.goal.as_ref()?)
+    }
+}
+
+/// Breadth-first search from the `sources`. The cost of each state is its
+/// distance (number of steps) to the nearest source.
+#[allow(dead_code)]
+pub fn bfs<S, I>(
+    sources: impl IntoIterator<Item = S>,
+    mut neighbors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Search<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut search = Search::new();
+    let mut queue = VecDeque::new();
+
+    for source in sources {
+        if !search.contains(&source) {
+            search.parents.insert(source.clone(), (None, 0));
+            queue.push_back(source);
+        }
+    }
+
+    while let Some(state) = queue.pop_front() {
+        search.order.push(state.clone());
+
+        if is_goal(&state) {
+            search.goal = Some(state);
+            break;
+        }
+
+        let dist = search.parents[&state].1;
+
+        for next in neighbors(&state) {
+            if !search.contains(&next) {
+                search
+                    .parents
+                    .insert(next.clone(), (Some(state.clone()), dist + 1));
+                queue.push_back(next);
+            }
+        }
+    }
+
+    search
+}
+
+/// Depth-first search from the `sources`. The cost of each state is its depth
+/// in the search tree.
+pub fn dfs<S, I>(
+    sources: impl IntoIterator<Item = S>,
+    mut neighbors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Search<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut search = Search::new();
+
+    // The sources are reversed so that the first one is visited first.
+    let mut stack: Vec<(S, Option<S>, usize)> = sources
+        .into_iter()
+        .map(|source| (source, None, 0))
+        .collect();
+    stack.reverse();
+
+    while let Some((state, parent, depth)) = stack.pop() {
+        if search.contains(&state) {
+            continue;
+        }
+
+        search.parents.insert(state.clone(), (parent, depth));
+        search.order.push(state.clone());
+
+        if is_goal(&state) {
+            search.goal = Some(state);
+            break;
+        }
+
+        for next in neighbors(&state) {
+            if !search.contains(&next) {
+                stack.push((next, Some(state.clone()), depth + 1));
+            }
+        }
+    }
+
+    search
+}
+
+/// A set of visited states, so that a search can mark them in a structure
+/// chosen by the caller (e.g., a BitGrid, when the states are points).
+pub trait Visited<S> {
+    fn is_visited(&self, state: &S) -> bool;
+
+    /// Mark the state as visited, returning whether it was not visited before.
+    fn visit(&mut self, state: &S) -> bool;
+}
+
+impl<S: Clone + Eq + Hash> Visited<S> for HashSet<S> {
+    fn is_visited(&self, state: &S) -> bool {
+        self.contains(state)
+    }
+
+    fn visit(&mut self, state: &S) -> bool {
+        self.insert(state.clone())
+    }
+}
+
+/// Depth-first search from the `sources`, like `dfs`, but marking the states
+/// in the `visited` set of the caller instead of keeping their predecessors.
+/// The states already in `visited` are skipped, so a set can be shared by
+/// several searches. Return the states in the order they were visited.
+pub fn dfs_in<S, I>(
+    sources: impl IntoIterator<Item = S>,
+    visited: &mut impl Visited<S>,
+    mut neighbors: impl FnMut(&S) -> I,
+) -> Vec<S>
+where
+    I: IntoIterator<Item = S>,
+{
+    let mut order = vec![];
+
+    // The sources are reversed so that the first one is visited first.
+    let mut stack: Vec<S> = sources.into_iter().collect();
+    stack.reverse();
+
+    while let Some(state) = stack.pop() {
+        if !visited.visit(&state) {
+            continue;
+        }
+
+        for next in neighbors(&state) {
+            if !visited.is_visited(&next) {
+                stack.push(next);
+            }
+        }
+
+        order.push(state);
+    }
+
+    order
+}
+
+/// Entry of the priority queue of Dijkstra and A*, ordered so that the
+/// `BinaryHeap` (a max-heap) pops the lowest priority first.
+struct Entry<S, C> {
+    priority: C,
+    cost: C,
+    state: S,
+    parent: Option<S>,
+}
+
+impl<S, C: Ord> PartialEq for Entry<S, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<S, C: Ord> Eq for Entry<S, C> {}
+
+impl<S, C: Ord> PartialOrd for Entry<S, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S, C: Ord> Ord for Entry<S, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+/// Dijkstra’s algorithm from the `sources`, in which `neighbors` returns each
+/// neighboring state with the (non-negative) cost of the edge to it. The cost
+/// of each state is the cost of the cheapest path from any source.
+pub fn dijkstra<S, C, I>(
+    sources: impl IntoIterator<Item = S>,
+    neighbors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Search<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Zero,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(sources, neighbors, |_| C::zero(), is_goal)
+}
+
+/// A* search from the `sources`, guided by a `heuristic` that estimates the
+/// cost from a state to the nearest goal. If the heuristic is consistent
+/// (i.e., it never drops by more than the cost of an edge), the goal is
+/// reached through the cheapest path.
+pub fn astar<S, C, I>(
+    sources: impl IntoIterator<Item = S>,
+    mut neighbors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Search<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Zero,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut search = Search::new();
+    let mut best: HashMap<S, C> = HashMap::new();
+    let mut heap = BinaryHeap::new();
+
+    for state in sources {
+        best.insert(state.clone(), C::zero());
+
+        heap.push(Entry {
+            priority: heuristic(&state),
+            cost: C::zero(),
+            state,
+            parent: None,
+        });
+    }
+
+    while let Some(Entry {
+        cost,
+        state,
+        parent,
+        ..
+    }) = heap.pop()
+    {
+        if search.contains(&state) {
+            continue;
+        }
+
+        search.parents.insert(state.clone(), (parent, cost));
+        search.order.push(state.clone());
+
+        if is_goal(&state) {
+            search.goal = Some(state);
+            break;
+        }
+
+        for (next, edge_cost) in neighbors(&state) {
+            let next_cost = cost + edge_cost;
+
+            if search.contains(&next) || best.get(&next).is_some_and(|&c| c <= next_cost) {
+                continue;
+            }
+
+            best.insert(next.clone(), next_cost);
+
+            heap.push(Entry {
+                priority: next_cost + heuristic(&next),
+                cost: next_cost,
+                state: next,
+                parent: Some(state.clone()),
+            });
+        }
+    }
+
+    search
+}
+
+/// Find the most expensive simple path (without repeated states) from the
+/// `source` to a goal state by exhaustive backtracking, returning its cost
+/// and its states. Its running time is exponential, so the graph should be
+/// small (e.g., already compressed to its junctions).
+#[allow(dead_code)]
+pub fn longest_path<S, C, I>(
+    source: S,
+    mut neighbors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<(C, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Zero,
+    I: IntoIterator<Item = (S, C)>,
+{
+    fn backtrack<S, C, I>(
+        state: &S,
+        cost: C,
+        path: &mut Vec<S>,
+        on_path: &mut HashSet<S>,
+        best: &mut Option<(C, Vec<S>)>,
+        neighbors: &mut impl FnMut(&S) -> I,
+        is_goal: &mut impl FnMut(&S) -> bool,
+    ) where
+        S: Clone + Eq + Hash,
+        C: Copy + Ord + Add<Output = C>,
+        I: IntoIterator<Item = (S, C)>,
+    {
+        if is_goal(state) {
+            if best
+                .as_ref()
+                .map_or(true, |(best_cost, _)| cost > *best_cost)
+            {
+                *best = Some((cost, path.clone()));
+            }
+
+            return;
+        }
+
+        for (next, edge_cost) in neighbors(state) {
+            if on_path.contains(&next) {
+                continue;
+            }
+
+            on_path.insert(next.clone());
+            path.push(next.clone());
+
+            backtrack(
+                &next,
+                cost + edge_cost,
+                path,
+                on_path,
+                best,
+                neighbors,
+                is_goal,
+            );
+
+            path.pop();
+            on_path.remove(&next);
+        }
+    }
+
+    let mut best = None;
+
+    backtrack(
+        &source,
+        C::zero(),
+        &mut vec![source.clone()],
+        &mut HashSet::from([source.clone()]),
+        &mut best,
+        &mut neighbors,
+        &mut is_goal,
+    );
+
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A small weighted, directed graph, in which the cheapest path from 0 to
+    /// 4 (0 → 1 → 2 → 4, with cost 4) is not the shortest (0 → 3 → 4, with
+    /// cost 10), and 5 is unreachable from 0.
+    fn edges(state: &u32) -> Vec<(u32, u32)> {
+        match state {
+            0 => vec![(1, 1), (3, 5)],
+            1 => vec![(2, 1)],
+            2 => vec![(4, 2), (0, 1)],
+            3 => vec![(4, 5)],
+            5 => vec![(4, 1)],
+            _ => vec![],
+        }
+    }
+
+    fn unweighted(state: &u32) -> Vec<u32> {
+        edges(state).into_iter().map(|(next, _)| next).collect()
+    }
+
+    #[test]
+    fn bfs_finds_the_fewest_steps() {
+        let search = bfs([0], unweighted, |&state| state == 4);
+
+        assert_eq!(search.goal, Some(4));
+        assert_eq!(search.goal_cost(), Some(2));
+        assert_eq!(search.goal_path(), Some(vec![0, 3, 4]));
+        assert_eq!(search.predecessor(&3), Some(&0));
+        assert_eq!(search.predecessor(&0), None);
+    }
+
+    #[test]
+    fn bfs_counts_steps_from_the_nearest_source() {
+        let search = bfs([0, 5], unweighted, |_| false);
+
+        assert_eq!(search.goal, None);
+        assert_eq!(search.len(), 6);
+        assert_eq!(search.cost(&4), Some(1));
+        assert_eq!(search.path(&4), Some(vec![5, 4]));
+        assert_eq!(search.cost(&2), Some(2));
+    }
+
+    #[test]
+    fn bfs_stops_at_the_goal() {
+        let search = bfs([0], unweighted, |&state| state == 1);
+
+        assert_eq!(search.order, vec![0, 1]);
+        assert!(search.contains(&3));
+        assert_eq!(search.cost(&2), None);
+    }
+
+    #[test]
+    fn bfs_without_reachable_goal() {
+        let search = bfs([0], unweighted, |&state| state == 5);
+
+        assert_eq!(search.goal, None);
+        assert_eq!(search.goal_cost(), None);
+        assert_eq!(search.goal_path(), None);
+        assert_eq!(search.order.len(), 5);
+        assert!(!search.contains(&5));
+    }
+
+    #[test]
+    fn dfs_goes_deep_first() {
+        let search = dfs([0], unweighted, |_| false);
+
+        // The neighbors are stacked in order, so the last one is visited first.
+        assert_eq!(search.order, vec![0, 3, 4, 1, 2]);
+        assert_eq!(search.cost(&4), Some(2));
+        assert_eq!(search.path(&4), Some(vec![0, 3, 4]));
+        assert_eq!(search.cost(&2), Some(2));
+        assert_eq!(search.predecessor(&2), Some(&1));
+    }
+
+    #[test]
+    fn dfs_visits_the_sources_in_order() {
+        let search = dfs([5, 0], unweighted, |&state| state == 2);
+
+        assert_eq!(search.order, vec![5, 4, 0, 3, 1, 2]);
+        assert_eq!(search.goal_path(), Some(vec![0, 1, 2]));
+    }
+
+    #[test]
+    fn dfs_without_reachable_goal() {
+        let search = dfs([3], unweighted, |&state| state == 0);
+
+        assert_eq!(search.goal, None);
+        assert_eq!(search.order, vec![3, 4]);
+    }
+
+    #[test]
+    fn dfs_in_agrees_with_dfs() {
+        let mut visited = HashSet::new();
+
+        assert_eq!(
+            dfs_in([5, 0], &mut visited, unweighted),
+            dfs([5, 0], unweighted, |_| false).order
+        );
+        assert_eq!(visited.len(), 6);
+    }
+
+    #[test]
+    fn dfs_in_skips_visited_states() {
+        let mut visited = HashSet::from([3]);
+
+        assert_eq!(dfs_in([0], &mut visited, unweighted), vec![0, 1, 2, 4]);
+
+        // Everything reachable from 3 was already visited.
+        assert_eq!(dfs_in([3], &mut visited, unweighted), vec![]);
+        assert_eq!(dfs_in([5], &mut visited, unweighted), vec![5]);
+    }
+
+    #[test]
+    fn dijkstra_finds_the_cheapest_path() {
+        let search = dijkstra([0], edges, |&state| state == 4);
+
+        assert_eq!(search.goal_cost(), Some(4));
+        assert_eq!(search.goal_path(), Some(vec![0, 1, 2, 4]));
+        assert!(!search.contains(&3), "3 costs more than the goal");
+    }
+
+    #[test]
+    fn dijkstra_from_several_sources() {
+        let search = dijkstra([3, 5], edges, |_| false);
+
+        assert_eq!(search.cost(&4), Some(1));
+        assert_eq!(search.path(&4), Some(vec![5, 4]));
+        assert_eq!(search.cost(&0), None);
+    }
+
+    #[test]
+    fn dijkstra_without_reachable_goal() {
+        let search = dijkstra([0], edges, |&state| state == 5);
+
+        assert_eq!(search.goal, None);
+        assert_eq!(search.goal_cost(), None);
+        assert_eq!(search.cost(&3), Some(5));
+    }
+
+    #[test]
+    fn astar_agrees_with_dijkstra() {
+        // The cheapest cost from each state to 4, which is consistent.
+        let heuristic = |state: &u32| match state {
+            0 => 4,
+            1 => 3,
+            2 => 2,
+            3 | 5 => 1,
+            _ => 0,
+        };
+
+        let search = astar([0], edges, heuristic, |&state| state == 4);
+
+        assert_eq!(search.goal_cost(), Some(4));
+        assert_eq!(search.goal_path(), Some(vec![0, 1, 2, 4]));
+
+        // Only the states on the cheapest path are expanded.
+        assert_eq!(search.order, vec![0, 1, 2, 4]);
+    }
+
+    #[test]
+    fn longest_path_avoids_repeated_states() {
+        let longest = longest_path(0, edges, |&state| state == 4);
+
+        assert_eq!(longest, Some((10, vec![0, 3, 4])));
+    }
+
+    #[test]
+    fn longest_path_without_reachable_goal() {
+        assert_eq!(longest_path(0, edges, |&state| state == 5), None);
+        assert_eq!(
+            longest_path(4, edges, |&state| state == 4),
+            Some((0, vec![4]))
+        );
+    }
+}
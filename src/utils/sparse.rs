@@ -0,0 +1,246 @@
+// Copyright (C) 2023 Matheus Fernandes Bigolin <mfrdrbigolin@disroot.org>
+// SPDX-License-Identifier: MIT
+
+// An unbounded grid that only stores its occupied tiles, for puzzles in which
+// the plane grows outward or is mostly empty.
+
+use std::{
+    collections::{hash_map, HashMap},
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+use super::{Direction, Matrix, Neighborhood, Point, Tile, Vector};
+
+/// A grid keyed by signed Points, which keeps track of the bounding box of
+/// its occupied tiles.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+    bounds: Option<(Point, Point)>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid::new()
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        self.cells.contains_key(&point)
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.cells.get(&point)
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.cells.get_mut(&point)
+    }
+
+    /// Insert a tile at a point, returning the tile previously there, if any.
+    pub fn insert(&mut self, point: Point, tile: T) -> Option<T> {
+        self.bounds = Some(match self.bounds {
+            None => (point, point),
+            Some((min, max)) => (
+                Point(min.0.min(point.0), min.1.min(point.1)),
+                Point(max.0.max(point.0), max.1.max(point.1)),
+            ),
+        });
+
+        self.cells.insert(point, tile)
+    }
+
+    /// Remove the tile at a point, returning it, if any.
+    pub fn remove(&mut self, point: Point) -> Option<T> {
+        let tile = self.cells.remove(&point)?;
+
+        // The bounding box only shrinks if the point was on its border.
+        if let Some((min, max)) = self.bounds {
+            if point.0 == min.0 || point.0 == max.0 || point.1 == min.1 || point.1 == max.1 {
+                self.bounds = Self::compute_bounds(self.cells.keys());
+            }
+        }
+
+        Some(tile)
+    }
+
+    fn compute_bounds<'a>(points: impl Iterator<Item = &'a Point>) -> Option<(Point, Point)> {
+        points.fold(None, |bounds, &point| match bounds {
+            None => Some((point, point)),
+            Some((min, max)) => Some((
+                Point(min.0.min(point.0), min.1.min(point.1)),
+                Point(max.0.max(point.0), max.1.max(point.1)),
+            )),
+        })
+    }
+
+    /// Return the top-left and bottom-right corners (both inclusive) of the
+    /// bounding box of the occupied tiles, if there is any.
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        self.bounds
+    }
+
+    /// Return the number of rows and columns of the bounding box.
+    pub fn dimensions(&self) -> (usize, usize) {
+        match self.bounds {
+            None => (0, 0),
+            Some((min, max)) => ((max.0 - min.0 + 1) as usize, (max.1 - min.1 + 1) as usize),
+        }
+    }
+
+    /// For each Direction, return, in a vector, the occupied neighbor point
+    /// with its associated Direction relative to this point.
+    pub fn get_neighbors(&self, point: Point) -> Vec<(Direction, Point)> {
+        Direction::ALL
+            .into_iter()
+            .map(|dir| (dir, point + dir.delta()))
+            .filter(|&(_, neighbor)| self.contains(neighbor))
+            .collect()
+    }
+
+    /// Return an iterator over the occupied neighbors of a point in a
+    /// `neighborhood`, with their (Δi, Δj) offsets relative to this point.
+    pub fn neighbors<'a>(
+        &'a self,
+        point: Point,
+        neighborhood: Neighborhood<'a>,
+    ) -> impl Iterator<Item = (Vector, Point)> + 'a {
+        neighborhood
+            .offsets()
+            .iter()
+            .map(move |&offset| (offset, point + offset))
+            .filter(move |&(_, neighbor)| self.contains(neighbor))
+    }
+
+    /// Return an iterator over the occupied points with their tiles, in
+    /// arbitrary order.
+    pub fn iter(&self) -> hash_map::Iter<'_, Point, T> {
+        self.cells.iter()
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        self.cells.keys().copied()
+    }
+}
+
+impl<T: Clone> SparseGrid<T> {
+    /// Create a SparseGrid from the tiles of a Matrix for which `keep` holds,
+    /// at the same points they have in the Matrix.
+    pub fn from_matrix(matrix: &Matrix<T>, mut keep: impl FnMut(&T) -> bool) -> Self {
+        matrix
+            .enumerate_points()
+            .filter(|(_, tile)| keep(tile))
+            .map(|(point, tile)| (point, tile.clone()))
+            .collect()
+    }
+}
+
+impl<T: Default + Clone> SparseGrid<T> {
+    /// Convert the bounding box of the SparseGrid into a dense Matrix, whose
+    /// top-left corner is the top-left corner of the bounding box. Empty
+    /// tiles are filled with the default tile.
+    pub fn to_matrix(&self) -> Matrix<T> {
+        let (rows, cols) = self.dimensions();
+        let mut matrix = Matrix::new(rows, cols);
+
+        if let Some((min, _)) = self.bounds {
+            for (&point, tile) in &self.cells {
+                matrix[Point::ORIGIN + (point - min)] = tile.clone();
+            }
+        }
+
+        matrix
+    }
+}
+
+impl<T: Tile> SparseGrid<T> {
+    /// Render the bounding box of the SparseGrid, with `empty` for the
+    /// unoccupied tiles.
+    pub fn render(&self, empty: char) -> String {
+        let Some((min, max)) = self.bounds else {
+            return String::new();
+        };
+
+        let mut rendered = String::new();
+
+        for i in min.0..=max.0 {
+            if i > min.0 {
+                rendered.push('\n');
+            }
+
+            for j in min.1..=max.1 {
+                rendered.push(self.get(Point(i, j)).map_or(empty, Tile::to_char));
+            }
+        }
+
+        rendered
+    }
+}
+
+impl<T: Tile> fmt::Display for SparseGrid<T> {
+    /// Print the bounding box of the SparseGrid, with '.' for the unoccupied
+    /// tiles.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render('.'))
+    }
+}
+
+impl<T> FromIterator<(Point, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point, T)>>(iter: I) -> Self {
+        let mut grid = SparseGrid::new();
+
+        for (point, tile) in iter {
+            grid.insert(point, tile);
+        }
+
+        grid
+    }
+}
+
+impl<T: Clone> From<&Matrix<T>> for SparseGrid<T> {
+    fn from(matrix: &Matrix<T>) -> Self {
+        SparseGrid::from_matrix(matrix, |_| true)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a SparseGrid<T> {
+    type Item = (&'a Point, &'a T);
+    type IntoIter = hash_map::Iter<'a, Point, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T> Index<Point> for SparseGrid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &Self::Output {
+        self.get(point)
+            .expect("the point should be occupied in the SparseGrid")
+    }
+}
+
+impl<T> IndexMut<Point> for SparseGrid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut Self::Output {
+        self.get_mut(point)
+            .expect("the point should be occupied in the SparseGrid")
+    }
+}
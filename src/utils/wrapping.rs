@@ -0,0 +1,92 @@
+// Copyright (C) 2023 Matheus Fernandes Bigolin <mfrdrbigolin@disroot.org>
+// SPDX-License-Identifier: MIT
+
+// A toroidal view of a Matrix, for puzzles in which the grid repeats itself
+// infinitely in every direction.
+
+use std::ops::Index;
+
+use super::{Direction, Matrix, Neighborhood, Point, Vector};
+
+/// A view of a Matrix that tiles the whole plane with copies of it. Every
+/// Point is valid, and it is mapped into the Matrix modulo its dimensions.
+#[derive(Debug)]
+pub struct Wrapping<'a, T> {
+    matrix: &'a Matrix<T>,
+}
+
+impl<T> Matrix<T> {
+    /// Return a wrap-around view of the Matrix.
+    pub fn wrapping(&self) -> Wrapping<'_, T> {
+        assert!(
+            self.rows > 0 && self.cols > 0,
+            "the Matrix should not be empty to wrap around"
+        );
+
+        Wrapping { matrix: self }
+    }
+}
+
+impl<'a, T> Wrapping<'a, T> {
+    pub fn matrix(&self) -> &'a Matrix<T> {
+        self.matrix
+    }
+
+    /// Map a Point into its equivalent Point inside the Matrix.
+    pub fn wrap(&self, point: Point) -> Point {
+        let (m, n) = (self.matrix.rows as i64, self.matrix.cols as i64);
+
+        Point(point.0.rem_euclid(m), point.1.rem_euclid(n))
+    }
+
+    /// Return the meta-coordinate of the copy of the Matrix that a Point
+    /// falls in, the original Matrix being at the origin.
+    pub fn meta(&self, point: Point) -> Point {
+        let (m, n) = (self.matrix.rows as i64, self.matrix.cols as i64);
+
+        Point(point.0.div_euclid(m), point.1.div_euclid(n))
+    }
+
+    pub fn get(&self, point: Point) -> &'a T {
+        &self.matrix[self.wrap(point)]
+    }
+
+    /// For each Direction, return, in a vector, the neighbor point with its
+    /// associated Direction relative to this point. Since the plane has no
+    /// boundary, there are always four neighbors.
+    pub fn get_neighbors(&self, point: Point) -> Vec<(Direction, Point)> {
+        Direction::ALL
+            .into_iter()
+            .map(|dir| (dir, point + dir.delta()))
+            .collect()
+    }
+
+    /// Return an iterator over all the neighbors of a point in a
+    /// `neighborhood`, with their (Δi, Δj) offsets relative to this point.
+    pub fn neighbors(
+        &self,
+        point: Point,
+        neighborhood: Neighborhood<'a>,
+    ) -> impl Iterator<Item = (Vector, Point)> + 'a {
+        neighborhood
+            .offsets()
+            .iter()
+            .map(move |&offset| (offset, point + offset))
+    }
+}
+
+impl<T> Clone for Wrapping<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Wrapping<'_, T> {}
+
+impl<T> Index<Point> for Wrapping<'_, T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &Self::Output {
+        self.get(point)
+    }
+}
@@ -0,0 +1,267 @@
+// Copyright (C) 2023 Matheus Fernandes Bigolin <mfrdrbigolin@disroot.org>
+// SPDX-License-Identifier: MIT
+
+// Derive macro for the tile enums of the solutions, implementing the
+// `crate::utils::Tile` trait from the characters annotated in each variant.
+// The generated code refers to `crate::utils` directly, so the derive can only
+// be used inside the aoc2023 crate.
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{
+    parse::ParseStream, parse_macro_input, Data, DeriveInput, Error, Fields, Ident, LitChar, Token,
+};
+
+/// Derive `Tile`, `Display`, and an `ALL` constant (with every variant, in
+/// declaration order) for an enum whose unit variants are annotated with the
+/// characters that represent them, e.g. `#[tile('#')]`. When a variant has
+/// several characters, like `#[tile('L', 'l')]`, all of them are parsed, but
+/// the first one is used to print it back. The characters of a variant marked
+/// as `internal`, like `#[tile('★', internal)]`, are parsed too, but are left
+/// out of the expected characters reported by `ParseTileError`.
+///
+/// The generated impls name `crate::utils::Tile` and
+/// `crate::utils::ParseTileError`, so the enum should be in the aoc2023 crate.
+#[proc_macro_derive(Tile, attributes(tile))]
+pub fn derive_tile(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match expand_tile(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+/// Parse the arguments of a `#[tile(…)]` attribute: the characters of the
+/// variant, optionally followed by `internal`.
+fn parse_tile_args(input: ParseStream) -> syn::Result<(Vec<LitChar>, bool)> {
+    let mut chars = vec![];
+    let mut internal = false;
+
+    while !input.is_empty() {
+        if input.peek(LitChar) {
+            chars.push(input.parse()?);
+        } else {
+            let ident: Ident = input.parse()?;
+
+            if ident != "internal" {
+                return Err(Error::new_spanned(
+                    ident,
+                    "expected a character or `internal`",
+                ));
+            }
+
+            internal = true;
+        }
+
+        if !input.is_empty() {
+            input.parse::<Token![,]>()?;
+        }
+    }
+
+    Ok((chars, internal))
+}
+
+fn expand_tile(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &input.ident;
+
+    let Data::Enum(data) = &input.data else {
+        return Err(Error::new_spanned(
+            input,
+            "Tile can only be derived for enums",
+        ));
+    };
+
+    let mut variants = vec![];
+    let mut from_arms = vec![];
+    let mut to_arms = vec![];
+    let mut used = vec![];
+    let mut expected = String::new();
+
+    for variant in &data.variants {
+        let ident = &variant.ident;
+
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(Error::new_spanned(
+                variant,
+                "Tile variants should not have fields",
+            ));
+        }
+
+        let attr = variant
+            .attrs
+            .iter()
+            .find(|attr| attr.path().is_ident("tile"))
+            .ok_or_else(|| {
+                Error::new_spanned(variant, "the variant should have a #[tile('…')] attribute")
+            })?;
+
+        let (chars, internal) = attr.parse_args_with(parse_tile_args)?;
+
+        let Some(symbol) = chars.first() else {
+            return Err(Error::new_spanned(
+                attr,
+                "the variant should have at least one character",
+            ));
+        };
+
+        for ch in &chars {
+            if used.contains(&ch.value()) {
+                return Err(Error::new_spanned(
+                    ch,
+                    "the character is already used by another variant",
+                ));
+            }
+
+            used.push(ch.value());
+
+            if !internal {
+                expected.push(ch.value());
+            }
+        }
+
+        variants.push(quote! { #name::#ident });
+        from_arms.push(quote! { #(#chars)|* => Ok(#name::#ident), });
+        to_arms.push(quote! { #name::#ident => #symbol, });
+    }
+
+    let num_variants = variants.len();
+    let expected = syn::LitStr::new(&expected, Span::call_site());
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Every variant of the tile, in declaration order.
+            #[allow(dead_code)]
+            pub const ALL: [#name #ty_generics; #num_variants] = [#(#variants),*];
+        }
+
+        impl #impl_generics crate::utils::Tile for #name #ty_generics #where_clause {
+            fn from_char(ch: char) -> Result<Self, crate::utils::ParseTileError> {
+                match ch {
+                    #(#from_arms)*
+                    _ => Err(crate::utils::ParseTileError { ch, expected: #expected }),
+                }
+            }
+
+            fn to_char(&self) -> char {
+                match self {
+                    #(#to_arms)*
+                }
+            }
+        }
+
+        impl #impl_generics ::std::fmt::Display for #name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                write!(f, "{}", crate::utils::Tile::to_char(self))
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::parse_quote;
+
+    use super::*;
+
+    fn expand_error(input: DeriveInput) -> String {
+        expand_tile(&input)
+            .expect_err("the derive should fail")
+            .to_string()
+    }
+
+    #[test]
+    fn expected_leaves_out_internal_chars() {
+        let tokens = expand_tile(&parse_quote! {
+            enum Card {
+                #[tile('★', internal)]
+                Joker,
+                #[tile('2')]
+                N2,
+                #[tile('A', 'a')]
+                A,
+            }
+        })
+        .expect("the derive should succeed")
+        .to_string();
+
+        assert!(tokens.contains(r#"expected : "2Aa""#), "{tokens}");
+        assert!(tokens.contains("'★' => Ok (Card :: Joker)"), "{tokens}");
+        assert!(tokens.contains("'A' | 'a' => Ok (Card :: A)"), "{tokens}");
+    }
+
+    #[test]
+    fn rejects_structs() {
+        let error = expand_error(parse_quote! {
+            struct Rock;
+        });
+
+        assert_eq!(error, "Tile can only be derived for enums");
+    }
+
+    #[test]
+    fn rejects_variants_with_fields() {
+        let error = expand_error(parse_quote! {
+            enum Rock {
+                #[tile('O')]
+                Round(u8),
+            }
+        });
+
+        assert_eq!(error, "Tile variants should not have fields");
+    }
+
+    #[test]
+    fn rejects_missing_attribute() {
+        let error = expand_error(parse_quote! {
+            enum Rock {
+                #[tile('O')]
+                Round,
+                Cube,
+            }
+        });
+
+        assert_eq!(error, "the variant should have a #[tile('…')] attribute");
+    }
+
+    #[test]
+    fn rejects_attribute_without_chars() {
+        let error = expand_error(parse_quote! {
+            enum Rock {
+                #[tile(internal)]
+                Round,
+            }
+        });
+
+        assert_eq!(error, "the variant should have at least one character");
+    }
+
+    #[test]
+    fn rejects_unknown_argument() {
+        let error = expand_error(parse_quote! {
+            enum Rock {
+                #[tile('O', hidden)]
+                Round,
+            }
+        });
+
+        assert_eq!(error, "expected a character or `internal`");
+    }
+
+    #[test]
+    fn rejects_duplicate_chars() {
+        let error = expand_error(parse_quote! {
+            enum Rock {
+                #[tile('O')]
+                Round,
+                #[tile('#', 'O')]
+                Cube,
+            }
+        });
+
+        assert_eq!(error, "the character is already used by another variant");
+    }
+}